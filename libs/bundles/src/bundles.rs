@@ -242,6 +242,15 @@ fn read_index_or_empty() -> Result<Index, BundleError> {
     }
 }
 
+/// Serializes a single catalogue or form as used for bundle objects
+pub fn item_to_json<T>(item: &T) -> Result<String, serde_json::Error>
+where
+    T: Serialize,
+{
+    let value = serde_json::to_value(item)?;
+    serde_json::to_string_pretty(&value)
+}
+
 fn add_item<T>(item: &T) -> Result<Object, ()>
 where
     T: Comparable + Serialize,
@@ -252,8 +261,7 @@ where
     if guid.is_empty() {
         return Err(());
     }
-    let value = serde_json::to_value(item).map_err(|_| ())?;
-    let json = serde_json::to_string_pretty(&value).map_err(|_| ())?;
+    let json = item_to_json(item).map_err(|_| ())?;
 
     let mut hasher = Sha256::new();
     hasher.update(&json);
@@ -618,6 +626,7 @@ pub fn export_bundle_versions(spec: &BundleVersionSpec) -> Result<OnkostarEditor
                 formulare_deaktivieren: None,
             },
             info_xml: InfoXML::from_bundle_version(bundle_version),
+            unknown_elements: vec![],
         });
     }
 
@@ -1,2 +1,3 @@
 pub mod osc;
+pub mod pattern;
 pub mod profile;
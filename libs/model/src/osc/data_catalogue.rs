@@ -58,6 +58,12 @@ pub struct DataCatalogue {
     ansichten: Option<Ansichten>,
 }
 
+impl DataCatalogue {
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries.entry
+    }
+}
+
 impl Named for DataCatalogue {
     fn get_name(&self) -> String {
         self.name.clone()
@@ -191,6 +197,33 @@ pub struct Entry {
     revision: u16,
 }
 
+impl Entry {
+    /// Name of the property catalogue used by this entry, if any
+    pub fn property_catalogue(&self) -> Option<&str> {
+        self.property_catalogue
+            .as_deref()
+            .filter(|name| !name.is_empty())
+    }
+}
+
+impl Named for Entry {
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+impl Revisioned for Entry {
+    fn get_revision(&self) -> u16 {
+        self.revision
+    }
+}
+
+impl Comparable for Entry {
+    fn get_guid(&self) -> String {
+        self.guid.clone()
+    }
+}
+
 impl Sortable for Entry {
     fn sorting_key(&self) -> String {
         self.name.clone()
@@ -0,0 +1,230 @@
+/*
+ * This file is part of osc-variant
+ *
+ * Copyright (C) 2026 the original author or authors.
+ *
+ * This program is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation; either version 2 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along
+ * with this program; if not, write to the Free Software Foundation, Inc.,
+ * 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
+ */
+
+use std::collections::{BTreeSet, HashMap};
+
+/// Directed graph of forms with edges from required forms to the forms requiring them
+pub(crate) struct DependencyGraph {
+    names: Vec<String>,
+    dependents: Vec<BTreeSet<usize>>,
+}
+
+impl DependencyGraph {
+    pub(crate) fn new(names: Vec<String>) -> Self {
+        let dependents = vec![BTreeSet::new(); names.len()];
+        DependencyGraph { names, dependents }
+    }
+
+    /// Adds the requirement of form `required` by form `form`. Self references are ignored.
+    pub(crate) fn add_requirement(&mut self, form: usize, required: usize) {
+        if form != required {
+            self.dependents[required].insert(form);
+        }
+    }
+
+    /// Returns the names of all forms requiring each other, sorted by name
+    pub(crate) fn cycles(&self) -> Vec<Vec<String>> {
+        let all = (0..self.names.len()).collect::<Vec<_>>();
+        let mut cycles = self
+            .components(&all)
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .map(|component| {
+                component
+                    .iter()
+                    .map(|&node| self.names[node].clone())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        cycles.sort();
+        cycles
+    }
+
+    /// Returns the given forms with required forms first.
+    /// Independent forms are ordered by name, forms requiring each other are kept together.
+    pub(crate) fn sorted(&self, nodes: &[usize]) -> Vec<usize> {
+        let components = self.components(nodes);
+        let component_of = components
+            .iter()
+            .enumerate()
+            .flat_map(|(component, members)| members.iter().map(move |&node| (node, component)))
+            .collect::<HashMap<_, _>>();
+
+        let mut successors = vec![BTreeSet::new(); components.len()];
+        let mut in_degree = vec![0; components.len()];
+        for &node in nodes {
+            for dependent in &self.dependents[node] {
+                if let (Some(&from), Some(&to)) =
+                    (component_of.get(&node), component_of.get(dependent))
+                    && from != to
+                    && successors[from].insert(to)
+                {
+                    in_degree[to] += 1;
+                }
+            }
+        }
+
+        let key = |component: usize| (self.names[components[component][0]].as_str(), component);
+        let mut ready = (0..components.len())
+            .filter(|&component| in_degree[component] == 0)
+            .map(key)
+            .collect::<BTreeSet<_>>();
+
+        let mut result = Vec::with_capacity(nodes.len());
+        while let Some((_, component)) = ready.pop_first() {
+            result.extend(&components[component]);
+            for &successor in &successors[component] {
+                in_degree[successor] -= 1;
+                if in_degree[successor] == 0 {
+                    ready.insert(key(successor));
+                }
+            }
+        }
+        result
+    }
+
+    /// Strongly connected components of the given nodes using Tarjan's algorithm.
+    /// Members of each component are sorted by name.
+    fn components(&self, nodes: &[usize]) -> Vec<Vec<usize>> {
+        let mut tarjan = Tarjan {
+            graph: self,
+            included: vec![false; self.names.len()],
+            index: vec![None; self.names.len()],
+            low: vec![0; self.names.len()],
+            on_stack: vec![false; self.names.len()],
+            stack: vec![],
+            next: 0,
+            components: vec![],
+        };
+        for &node in nodes {
+            tarjan.included[node] = true;
+        }
+        for &node in nodes {
+            if tarjan.index[node].is_none() {
+                tarjan.visit(node);
+            }
+        }
+        tarjan.components
+    }
+}
+
+struct Tarjan<'a> {
+    graph: &'a DependencyGraph,
+    included: Vec<bool>,
+    index: Vec<Option<usize>>,
+    low: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    next: usize,
+    components: Vec<Vec<usize>>,
+}
+
+impl Tarjan<'_> {
+    fn visit(&mut self, node: usize) {
+        self.index[node] = Some(self.next);
+        self.low[node] = self.next;
+        self.next += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+
+        let graph = self.graph;
+        for &dependent in &graph.dependents[node] {
+            if !self.included[dependent] {
+                continue;
+            }
+            match self.index[dependent] {
+                None => {
+                    self.visit(dependent);
+                    self.low[node] = self.low[node].min(self.low[dependent]);
+                }
+                Some(index) if self.on_stack[dependent] => {
+                    self.low[node] = self.low[node].min(index);
+                }
+                _ => {}
+            }
+        }
+
+        if self.index[node] == Some(self.low[node]) {
+            let mut component = vec![];
+            while let Some(member) = self.stack.pop() {
+                self.on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            component.sort_by(|&a, &b| graph.names[a].cmp(&graph.names[b]).then(a.cmp(&b)));
+            self.components.push(component);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::osc::dependencies::DependencyGraph;
+
+    fn graph() -> DependencyGraph {
+        DependencyGraph::new(
+            ["D", "C", "B", "A", "E"]
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn should_sort_by_name_without_requirements() {
+        let graph = graph();
+
+        assert_eq!(graph.sorted(&[0, 1, 2, 3, 4]), vec![3, 2, 1, 0, 4]);
+    }
+
+    #[test]
+    fn should_sort_required_forms_first() {
+        let mut graph = graph();
+        // "A" requires "D", "B" requires "C"
+        graph.add_requirement(3, 0);
+        graph.add_requirement(2, 1);
+
+        assert_eq!(graph.sorted(&[0, 1, 2, 3, 4]), vec![1, 2, 0, 3, 4]);
+        assert!(graph.cycles().is_empty());
+    }
+
+    #[test]
+    fn should_only_sort_given_forms() {
+        let mut graph = graph();
+        graph.add_requirement(3, 0);
+
+        assert_eq!(graph.sorted(&[2, 3, 4]), vec![3, 2, 4]);
+    }
+
+    #[test]
+    fn should_report_cycles_and_keep_forms_together() {
+        let mut graph = graph();
+        // "A" and "E" require each other, "C" requires "A"
+        graph.add_requirement(3, 4);
+        graph.add_requirement(4, 3);
+        graph.add_requirement(1, 3);
+        graph.add_requirement(2, 2);
+
+        assert_eq!(graph.cycles(), vec![vec!["A".to_string(), "E".to_string()]]);
+        assert_eq!(graph.sorted(&[0, 1, 2, 3, 4]), vec![3, 4, 2, 1, 0]);
+    }
+}
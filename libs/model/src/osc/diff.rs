@@ -0,0 +1,249 @@
+/*
+ * This file is part of osc-variant
+ *
+ * Copyright (C) 2026 the original author or authors.
+ *
+ * This program is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation; either version 2 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along
+ * with this program; if not, write to the Free Software Foundation, Inc.,
+ * 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
+ */
+
+//! Structural comparison of catalogues and forms.
+//!
+//! Lists of elements, e.g. form entries, catalogue versions or plausibility rules, are compared
+//! by the name of the element, independent of their order.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Properties used to identify an element within a list
+const IDENTITY_PROPERTIES: [&str; 5] = ["Name", "Code", "Bezeichnung", "VersionNumber", "GUID"];
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Change {
+    /// Element only contained in the second item
+    Added { element: String },
+    /// Element only contained in the first item
+    Removed { element: String },
+    /// Property of the item or of an element with old and new value, `None` if not present
+    Changed {
+        element: Option<String>,
+        property: String,
+        old: Option<String>,
+        new: Option<String>,
+    },
+}
+
+/// Returns all changes between two versions of an item
+pub fn diff(a: &impl Serialize, b: &impl Serialize) -> Vec<Change> {
+    let a = serde_json::to_value(a).unwrap_or(Value::Null);
+    let b = serde_json::to_value(b).unwrap_or(Value::Null);
+
+    let mut changes = vec![];
+    diff_values(&[], &[], &a, &b, &mut changes);
+    changes
+}
+
+fn diff_values(
+    element: &[String],
+    property: &[&str],
+    a: &Value,
+    b: &Value,
+    changes: &mut Vec<Change>,
+) {
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => {
+            let keys = a.keys().chain(b.keys().filter(|key| !a.contains_key(*key)));
+            for key in keys {
+                let mut property = property.to_vec();
+                property.push(key);
+                diff_values(
+                    element,
+                    &property,
+                    a.get(key).unwrap_or(&Value::Null),
+                    b.get(key).unwrap_or(&Value::Null),
+                    changes,
+                );
+            }
+        }
+        (Value::Array(a), Value::Array(b))
+            if a.iter().chain(b.iter()).all(Value::is_object)
+                && !(a.is_empty() && b.is_empty()) =>
+        {
+            let name = property.last().copied().unwrap_or_default();
+            let a = identified(name, a);
+            let b = identified(name, b);
+
+            for (label, value_a) in &a {
+                let mut element = element.to_vec();
+                element.push(label.clone());
+                match b.iter().find(|(other, _)| other == label) {
+                    Some((_, value_b)) => diff_values(&element, &[], value_a, value_b, changes),
+                    None => changes.push(Change::Removed {
+                        element: element.join(" > "),
+                    }),
+                }
+            }
+            for (label, _) in b
+                .iter()
+                .filter(|(label, _)| !a.iter().any(|(other, _)| other == label))
+            {
+                let mut element = element.to_vec();
+                element.push(label.clone());
+                changes.push(Change::Added {
+                    element: element.join(" > "),
+                });
+            }
+        }
+        (Value::Null, Value::Object(_)) => {
+            diff_values(element, property, &Value::Object(Map::new()), b, changes);
+        }
+        (Value::Object(_), Value::Null) => {
+            diff_values(element, property, a, &Value::Object(Map::new()), changes);
+        }
+        (Value::Null, Value::Array(_)) => {
+            diff_values(element, property, &Value::Array(vec![]), b, changes);
+        }
+        (Value::Array(_), Value::Null) => {
+            diff_values(element, property, a, &Value::Array(vec![]), changes);
+        }
+        _ => {
+            let old = scalar(a);
+            let new = scalar(b);
+            if old != new {
+                changes.push(Change::Changed {
+                    element: if element.is_empty() {
+                        None
+                    } else {
+                        Some(element.join(" > "))
+                    },
+                    property: property.join("."),
+                    old,
+                    new,
+                });
+            }
+        }
+    }
+}
+
+/// Labels elements of a list by name and identifying property
+fn identified<'a>(name: &str, values: &'a [Value]) -> Vec<(String, &'a Value)> {
+    let mut result: Vec<(String, &Value)> = vec![];
+    for (index, value) in values.iter().enumerate() {
+        let identity = IDENTITY_PROPERTIES
+            .iter()
+            .find_map(|property| value.get(property).and_then(scalar));
+        let mut label = match identity {
+            Some(identity) => format!("{name} '{identity}'"),
+            None => format!("{name} #{}", index + 1),
+        };
+        if result.iter().any(|(other, _)| *other == label) {
+            label = format!("{label} #{}", index + 1);
+        }
+        result.push((label, value));
+    }
+    result
+}
+
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(value) => Some(value.clone()),
+        Value::Array(values) => Some(
+            values
+                .iter()
+                .filter_map(scalar)
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+        value => Some(value.to_string()),
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::osc::diff::{Change, diff};
+    use crate::osc::onkostar_editor::OnkostarEditor;
+
+    fn editor(content: &str) -> OnkostarEditor {
+        OnkostarEditor::from_str(content).unwrap()
+    }
+
+    #[test]
+    fn should_not_find_changes_in_equal_items() {
+        let a = editor(include_str!("../../tests/test.osc"));
+        let b = editor(include_str!("../../tests/test.osc"));
+
+        assert!(diff(&a.editor.data_form[0], &b.editor.data_form[0]).is_empty());
+    }
+
+    #[test]
+    fn should_find_changed_entry_property() {
+        let a = editor(include_str!("../../tests/test.osc"));
+        let b = editor(&include_str!("../../tests/test.osc").replace(
+            "<Hinweis>Wählen Sie hier den Wert aus.</Hinweis>",
+            "<Hinweis>Neuer Hinweis</Hinweis>",
+        ));
+
+        assert_eq!(
+            diff(&a.editor.data_form[0], &b.editor.data_form[0]),
+            vec![Change::Changed {
+                element: Some("Entry 'Auswahl'".to_string()),
+                property: "Hinweis".to_string(),
+                old: Some("Wählen Sie hier den Wert aus.".to_string()),
+                new: Some("Neuer Hinweis".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn should_find_changed_form_property() {
+        let a = editor(include_str!("../../tests/test.osc"));
+        let b = editor(&include_str!("../../tests/test.osc").replace(
+            "<Description>Test Hauptformular</Description>",
+            "<Description>Neu</Description>",
+        ));
+
+        assert_eq!(
+            diff(&a.editor.data_form[0], &b.editor.data_form[0]),
+            vec![Change::Changed {
+                element: None,
+                property: "Description".to_string(),
+                old: Some("Test Hauptformular".to_string()),
+                new: Some("Neu".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn should_find_added_and_removed_entries() {
+        let a = editor(include_str!("../../tests/test.osc"));
+        let b = editor(
+            &include_str!("../../tests/test.osc")
+                .replace("<Name>Auswahl</Name>", "<Name>Neue Auswahl</Name>"),
+        );
+
+        let actual = diff(&a.editor.data_form[0], &b.editor.data_form[0]);
+
+        assert!(actual.contains(&Change::Removed {
+            element: "Entry 'Auswahl'".to_string()
+        }));
+        assert!(actual.contains(&Change::Added {
+            element: "Entry 'Neue Auswahl'".to_string()
+        }));
+    }
+}
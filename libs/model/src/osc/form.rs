@@ -23,15 +23,25 @@ use crate::osc::other::Entry;
 use crate::osc::requirements::{Requirement, Requires};
 use crate::osc::{
     Ansichten, Comparable, Entries, FolderContained, Kennzahlen, MenuCategory, Named,
-    PlausibilityRules, PunkteKategorien, Revisioned, Script, Sortable, TypedEntry,
+    PlausibilityRules, PunkteKategorien, Revisioned, Script, Sortable, TypedEntry, unescape_script,
 };
 use crate::osc::{Haeufigkeiten, Ordner};
 use serde::{Deserialize, Serialize};
-use std::cmp::Ordering;
 use std::collections::HashSet;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 
+/// Names of all form events supporting scripts
+pub const SCRIPT_EVENTS: [&str; 7] = [
+    "ScriptBeimSchliessen",
+    "ScriptBeimSpeichern",
+    "ScriptBeimNeuanlegen",
+    "ScriptBeimBearbeiten",
+    "ScriptBeimKopieren",
+    "ScriptBeimImport",
+    "ScriptBeimAnonymisieren",
+];
+
 #[derive(Debug)]
 pub struct DataFormType;
 
@@ -395,21 +405,6 @@ where
     fn get_guid(&self) -> String {
         self.guid.clone()
     }
-
-    fn compare_by_requirement(a: &Self, b: &Self) -> Ordering {
-        if a.get_name() == b.get_name()
-            || a.is_system_library_content()
-            || b.is_system_library_content()
-        {
-            return Ordering::Equal;
-        }
-
-        if a.requires_form_reference(&b.get_name()) || a.requires_subform(&b.get_name()) {
-            return Ordering::Greater;
-        }
-
-        Ordering::Less
-    }
 }
 
 impl<Type: 'static> FolderContained for Form<Type> {
@@ -421,6 +416,166 @@ impl<Type: 'static> FolderContained for Form<Type> {
     }
 }
 
+impl<Type> Form<Type> {
+    /// Returns true if the form shows the history button
+    pub fn has_show_history_button(&self) -> bool {
+        self.show_history_button == Some(true)
+    }
+
+    /// Description of the form, if any
+    pub fn description(&self) -> Option<&str> {
+        self.description
+            .as_deref()
+            .filter(|description| !description.is_empty())
+    }
+
+    /// All form fields of the form
+    pub fn entries(&self) -> &[Entry] {
+        self.entries
+            .as_ref()
+            .map_or(&[], |entries| entries.entry.as_slice())
+    }
+
+    /// Names of the data catalogues listed for this form
+    pub fn data_catalogues(&self) -> &[String] {
+        self.data_catalogues
+            .as_ref()
+            .map_or(&[], |data_catalogues| {
+                data_catalogues.data_catalogue.as_slice()
+            })
+    }
+
+    fn event_script(&self, event: &str) -> Option<&Option<Script>> {
+        match event {
+            "ScriptBeimSchliessen" => Some(&self.script_beim_schliessen),
+            "ScriptBeimSpeichern" => Some(&self.script_beim_speichern),
+            "ScriptBeimNeuanlegen" => Some(&self.script_beim_neuanlegen),
+            "ScriptBeimBearbeiten" => Some(&self.script_beim_bearbeiten),
+            "ScriptBeimKopieren" => Some(&self.script_beim_kopieren),
+            "ScriptBeimImport" => Some(&self.script_beim_import),
+            "ScriptBeimAnonymisieren" => Some(&self.script_beim_anonymisieren),
+            _ => None,
+        }
+    }
+
+    fn event_script_mut(&mut self, event: &str) -> Option<&mut Option<Script>> {
+        match event {
+            "ScriptBeimSchliessen" => Some(&mut self.script_beim_schliessen),
+            "ScriptBeimSpeichern" => Some(&mut self.script_beim_speichern),
+            "ScriptBeimNeuanlegen" => Some(&mut self.script_beim_neuanlegen),
+            "ScriptBeimBearbeiten" => Some(&mut self.script_beim_bearbeiten),
+            "ScriptBeimKopieren" => Some(&mut self.script_beim_kopieren),
+            "ScriptBeimImport" => Some(&mut self.script_beim_import),
+            "ScriptBeimAnonymisieren" => Some(&mut self.script_beim_anonymisieren),
+            _ => None,
+        }
+    }
+
+    /// Scripts of form events, e.g. "ScriptBeimSpeichern", by event name
+    pub fn event_scripts(&self) -> Vec<(&'static str, &Script)> {
+        SCRIPT_EVENTS
+            .into_iter()
+            .filter_map(|event| {
+                self.event_script(event)?
+                    .as_ref()
+                    .map(|script| (event, script))
+            })
+            .collect()
+    }
+
+    /// All scripts of the form and its form fields with their location in the form
+    pub fn scripts(&self) -> Vec<(String, &Script)> {
+        let mut result = self
+            .event_scripts()
+            .into_iter()
+            .map(|(event, script)| (event.to_string(), script))
+            .collect::<Vec<_>>();
+
+        for entry in self.entries() {
+            if let Some(ref script) = entry.scripts {
+                result.push((format!("Formularfeld '{}'", entry.get_name()), script));
+            }
+        }
+
+        result
+    }
+
+    /// Replaces the script of the given form event with the not yet escaped code or removes it.
+    /// Returns `None` for unknown events and whether the script was changed otherwise.
+    pub fn update_event_script(&mut self, event: &str, code: Option<&str>) -> Option<bool> {
+        let script = self.event_script_mut(event)?;
+        Some(update_script(script, code))
+    }
+
+    /// Replaces the script of the given form field with the not yet escaped code or removes it.
+    /// The revision of a changed form field is increased.
+    /// Returns `None` for unknown form fields and whether the script was changed otherwise.
+    pub fn update_entry_script(&mut self, name: &str, code: Option<&str>) -> Option<bool> {
+        let entry = self
+            .entries
+            .as_mut()?
+            .entry
+            .iter_mut()
+            .find(|entry| entry.get_name() == name)?;
+        let changed = update_script(&mut entry.scripts, code);
+        if changed {
+            entry.revision += 1;
+        }
+        Some(changed)
+    }
+
+    /// Names of all plausibility rules with the names of the form fields they use
+    pub fn plausibility_rule_entry_names(&self) -> Vec<(String, &[String])> {
+        let Some(ref plausibility_rules) = self.plausibility_rules else {
+            return vec![];
+        };
+
+        plausibility_rules
+            .plausibility_rule
+            .iter()
+            .flatten()
+            .map(|item| {
+                (
+                    item.bezeichnung.clone(),
+                    item.data_form_entries
+                        .entry_name
+                        .as_deref()
+                        .unwrap_or_default(),
+                )
+            })
+            .collect()
+    }
+
+    /// Removes trailing whitespace from the names of all plausibility rules.
+    /// Returns the changed names without trailing whitespace.
+    pub fn trim_plausibility_rule_names(&mut self) -> Vec<String> {
+        let Some(ref mut plausibility_rules) = self.plausibility_rules else {
+            return vec![];
+        };
+
+        plausibility_rules
+            .plausibility_rule
+            .iter_mut()
+            .flatten()
+            .filter(|item| item.bezeichnung.trim_end() != item.bezeichnung)
+            .map(|item| {
+                item.bezeichnung = item.bezeichnung.trim_end().to_string();
+                item.bezeichnung.clone()
+            })
+            .collect()
+    }
+}
+
+/// Replaces the script if the code differs from the unescaped code of the current script
+fn update_script(script: &mut Option<Script>, code: Option<&str>) -> bool {
+    let current = script.as_ref().map(|script| unescape_script(script.code()));
+    if current.as_deref() == code {
+        return false;
+    }
+    *script = code.map(Script::new);
+    true
+}
+
 impl Form<DataFormReferenceType> {
     /// Create a new minimal form reference with given form name
     pub fn new_form_reference(reference_name: &str) -> Self {
@@ -508,9 +663,82 @@ pub struct DataFormEntries {
 mod tests {
     use std::str::FromStr;
 
-    use crate::osc::Script;
     use crate::osc::onkostar_editor::OnkostarEditor;
-    use crate::profile::Profile;
+    use crate::osc::{Named, Script};
+    use crate::profile::{Profile, UnmatchedRule};
+
+    #[test]
+    fn should_trim_plausibility_rule_names() {
+        let mut onkostar_editor =
+            OnkostarEditor::from_str(&include_str!("../../tests/test.osc").replace(
+                "<PlausibilityRules/>",
+                "<PlausibilityRules>
+                  <PlausibilityRule>
+                    <Type>Fehler</Type>
+                    <Description>Test</Description>
+                    <Bezeichnung>Testregel  </Bezeichnung>
+                    <Active>true</Active>
+                    <Editable>true</Editable>
+                    <Valid>true</Valid>
+                    <Statusauswirkung>keine</Statusauswirkung>
+                    <DataFormEntries/>
+                  </PlausibilityRule>
+                </PlausibilityRules>",
+            ))
+            .unwrap();
+
+        let form = &mut onkostar_editor.editor.data_form[0];
+
+        assert_eq!(form.trim_plausibility_rule_names(), vec!["Testregel"]);
+        assert!(form.trim_plausibility_rule_names().is_empty());
+    }
+
+    #[test]
+    fn should_find_scripts_of_form_fields() {
+        let onkostar_editor =
+            OnkostarEditor::from_str(include_str!("../../tests/test.osc")).unwrap();
+
+        let scripts = onkostar_editor.editor.data_form[0].scripts();
+
+        assert_eq!(scripts.len(), 1);
+        assert_eq!(scripts[0].0, "Formularfeld 'Formularverweis'");
+        assert_eq!(scripts[0].1.code(), "/* test */");
+    }
+
+    #[test]
+    fn should_update_scripts_only_if_changed() {
+        let mut onkostar_editor =
+            OnkostarEditor::from_str(include_str!("../../tests/test.osc")).unwrap();
+        let form = &mut onkostar_editor.editor.data_form[0];
+
+        assert_eq!(
+            form.update_entry_script("Formularverweis", Some("/* test */")),
+            Some(false)
+        );
+        assert_eq!(
+            form.update_entry_script("Formularverweis", Some("// a\nb();")),
+            Some(true)
+        );
+        assert_eq!(form.update_entry_script("Unbekannt", None), None);
+        assert_eq!(
+            form.update_event_script("ScriptBeimSpeichern", Some("c();")),
+            Some(true)
+        );
+        assert_eq!(form.update_event_script("ScriptBeimUnbekannt", None), None);
+
+        let scripts = form.scripts();
+        assert_eq!(scripts.len(), 2);
+        assert_eq!(scripts[0].0, "ScriptBeimSpeichern");
+        assert_eq!(scripts[1].1.code(), "// a&#10;b();");
+        assert_eq!(
+            form.entries()
+                .iter()
+                .find(|entry| entry.get_name() == "Formularverweis")
+                .unwrap()
+                .revision,
+            21
+        );
+    }
 
     #[test]
     fn should_change_dataform_entry_default_value() {
@@ -565,6 +793,274 @@ mod tests {
         assert_eq!(actual.entry[2].default_value, "");
     }
 
+    #[test]
+    fn should_report_unmatched_profile_rules() {
+        let onkostar_editor = OnkostarEditor::from_str(include_str!("../../tests/test.osc"));
+
+        assert!(onkostar_editor.is_ok());
+        let mut onkostar_editor = onkostar_editor.unwrap();
+
+        let profile = "forms:
+               - name: 'Hauptformular'
+                 form_references:
+                   - name: Formularverweis
+                     anzeige: 'Datum: {Datum}'
+                   - name: Auswahl
+                     anzeige: 'Datum: {Datum}'
+                 form_fields:
+                   - name: Auswahl
+                     default_value: 'B'
+                   - name: Umbenannt
+                     hide: true
+               - name: 'Unbekannt'
+                 form_fields:
+                   - name: Auswahl
+                     hide: true
+            ";
+
+        let profile = Profile::from_str(profile);
+        assert!(profile.is_ok());
+        let profile = profile.unwrap();
+
+        let unmatched = onkostar_editor.apply_profile(&profile);
+
+        assert_eq!(
+            unmatched,
+            vec![
+                UnmatchedRule::FormReference("Hauptformular".to_string(), "Auswahl".to_string()),
+                UnmatchedRule::FormField("Hauptformular".to_string(), "Umbenannt".to_string()),
+                UnmatchedRule::Form("Unbekannt".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_layer_exact_profile_rules_over_patterns() {
+        let onkostar_editor = OnkostarEditor::from_str(include_str!("../../tests/test.osc"));
+
+        assert!(onkostar_editor.is_ok());
+        let mut onkostar_editor = onkostar_editor.unwrap();
+
+        let profile = "forms:
+               - name: 'Hauptformular'
+                 form_fields:
+                   - name: Button1
+                     default_value: 'D'
+               - name: 'Haupt*'
+                 form_fields:
+                   - name: Auswahl
+                     default_value: 'B'
+                   - name: '/Aus.*/'
+                     default_value: 'A'
+                     hinweis: 'Hinweis'
+                   - name: 'Button?'
+                     default_value: 'C'
+                     hinweis: 'Button'
+                   - name: 'Nicht*'
+                     hide: true
+            ";
+
+        let profile = Profile::from_str(profile);
+        assert!(profile.is_ok());
+        let profile = profile.unwrap();
+
+        let unmatched = onkostar_editor.apply_profile(&profile);
+
+        let Some(actual) = &onkostar_editor.editor.data_form[0].entries else {
+            panic!()
+        };
+
+        let entry = |name: &str| {
+            actual
+                .entry
+                .iter()
+                .find(|entry| entry.get_name() == name)
+                .map(|entry| (entry.default_value.clone(), entry.hinweis.clone()))
+        };
+        assert_eq!(
+            entry("Auswahl"),
+            Some(("B".to_string(), Some("Hinweis".to_string())))
+        );
+        assert_eq!(
+            entry("Button1"),
+            Some(("D".to_string(), Some("Button".to_string())))
+        );
+        assert_eq!(
+            unmatched,
+            vec![UnmatchedRule::FormField(
+                "Haupt*".to_string(),
+                "Nicht*".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn should_update_entry_attributes_and_revisions() {
+        let onkostar_editor = OnkostarEditor::from_str(include_str!("../../tests/test.osc"));
+
+        assert!(onkostar_editor.is_ok());
+        let mut onkostar_editor = onkostar_editor.unwrap();
+
+        let profile = "forms:
+               - name: 'Hauptformular'
+                 form_fields:
+                   - name: Auswahl
+                     mandatory: false
+                     readonly: true
+                     active: false
+                     description: 'Neue Beschreibung'
+                     hinweis: 'Neuer Hinweis'
+                     position: 3.0
+                     element_parent: Feldgruppe2
+            ";
+
+        let profile = Profile::from_str(profile);
+        assert!(profile.is_ok());
+        let profile = profile.unwrap();
+
+        let form_revision = onkostar_editor.editor.data_form[0].revision;
+        let entry_revision = |onkostar_editor: &OnkostarEditor| {
+            onkostar_editor.editor.data_form[0]
+                .entries()
+                .iter()
+                .find(|entry| entry.get_name() == "Auswahl")
+                .map(|entry| entry.revision)
+        };
+        let revision = entry_revision(&onkostar_editor).unwrap();
+
+        onkostar_editor.apply_profile(&profile);
+
+        let Some(actual) = onkostar_editor.editor.data_form[0]
+            .entries()
+            .iter()
+            .find(|entry| entry.get_name() == "Auswahl")
+        else {
+            panic!()
+        };
+
+        assert_eq!(actual.mandatory(), "false");
+        assert!(actual.read_only());
+        assert!(!actual.active());
+        assert_eq!(actual.description, "Neue Beschreibung");
+        assert_eq!(actual.hinweis, Some("Neuer Hinweis".to_string()));
+        assert_eq!(actual.position, "3.0");
+        assert_eq!(actual.element_parent(), Some("Feldgruppe2"));
+        assert_eq!(actual.revision, revision + 1);
+        assert_eq!(
+            onkostar_editor.editor.data_form[0].revision,
+            form_revision + 1
+        );
+
+        // No further changes if applied again
+        onkostar_editor.apply_profile(&profile);
+
+        assert_eq!(entry_revision(&onkostar_editor), Some(revision + 1));
+        assert_eq!(
+            onkostar_editor.editor.data_form[0].revision,
+            form_revision + 1
+        );
+    }
+
+    #[test]
+    fn should_update_revision_of_hidden_entry() {
+        let onkostar_editor = OnkostarEditor::from_str(include_str!("../../tests/test.osc"));
+
+        assert!(onkostar_editor.is_ok());
+        let mut onkostar_editor = onkostar_editor.unwrap();
+
+        let profile = "forms:
+               - name: 'Hauptformular'
+                 form_fields:
+                   - name: Auswahl
+                     hide: true
+            ";
+
+        let profile = Profile::from_str(profile);
+        assert!(profile.is_ok());
+        let profile = profile.unwrap();
+
+        let form_revision = onkostar_editor.editor.data_form[0].revision;
+        let entry_revision = |onkostar_editor: &OnkostarEditor| {
+            onkostar_editor.editor.data_form[0]
+                .entries()
+                .iter()
+                .find(|entry| entry.get_name() == "Auswahl")
+                .map(|entry| entry.revision)
+        };
+        let revision = entry_revision(&onkostar_editor).unwrap();
+
+        onkostar_editor.apply_profile(&profile);
+
+        assert_eq!(entry_revision(&onkostar_editor), Some(revision + 1));
+        assert_eq!(
+            onkostar_editor.editor.data_form[0].revision,
+            form_revision + 1
+        );
+
+        // No further changes if applied again
+        onkostar_editor.apply_profile(&profile);
+
+        assert_eq!(entry_revision(&onkostar_editor), Some(revision + 1));
+        assert_eq!(
+            onkostar_editor.editor.data_form[0].revision,
+            form_revision + 1
+        );
+    }
+
+    #[test]
+    fn should_not_move_form_fields_into_unknown_element_parent() {
+        let onkostar_editor = OnkostarEditor::from_str(include_str!("../../tests/test.osc"));
+
+        assert!(onkostar_editor.is_ok());
+        let mut onkostar_editor = onkostar_editor.unwrap();
+
+        let profile = "forms:
+               - name: 'Hauptformular'
+                 form_fields:
+                   - name: Auswahl
+                     element_parent: Unbekannt
+                     description: 'Neue Beschreibung'
+                   - name: Button1
+                     element_parent: Button1
+                   - name: Formularverweis
+                     element_parent: ''
+            ";
+
+        let profile = Profile::from_str(profile);
+        assert!(profile.is_ok());
+        let profile = profile.unwrap();
+
+        let unmatched = onkostar_editor.apply_profile(&profile);
+
+        let element_parent = |name: &str| {
+            onkostar_editor.editor.data_form[0]
+                .entries()
+                .iter()
+                .find(|entry| entry.get_name() == name)
+                .map(|entry| (entry.element_parent(), entry.description.as_str()))
+        };
+        assert_eq!(element_parent("Auswahl"), Some((None, "Neue Beschreibung")));
+        assert_eq!(
+            element_parent("Button1").map(|(parent, _)| parent),
+            Some(Some("Feldgruppe2"))
+        );
+        assert_eq!(
+            unmatched,
+            vec![
+                UnmatchedRule::ElementParent(
+                    "Hauptformular".to_string(),
+                    "Button1".to_string(),
+                    "Button1".to_string()
+                ),
+                UnmatchedRule::ElementParent(
+                    "Hauptformular".to_string(),
+                    "Auswahl".to_string(),
+                    "Unbekannt".to_string()
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn should_change_menu_category() {
         let onkostar_editor = OnkostarEditor::from_str(include_str!("../../tests/test.osc"));
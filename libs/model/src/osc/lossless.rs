@@ -0,0 +1,859 @@
+/*
+ * This file is part of osc-variant
+ *
+ * Copyright (C) 2026 the original author or authors.
+ *
+ * This program is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation; either version 2 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along
+ * with this program; if not, write to the Free Software Foundation, Inc.,
+ * 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
+ */
+
+//! Lossless deserialization of OSC content.
+//!
+//! Elements and attributes unknown to the model are skipped while deserializing instead of
+//! aborting. They are kept together with the identity of their parent element and the known
+//! elements preceding them, so they can be written back into the serialized content.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Formatter;
+
+use quick_xml::Reader;
+use quick_xml::escape::unescape;
+use quick_xml::events::{BytesStart, Event};
+use serde::de::value::StringDeserializer;
+use serde::de::{
+    DeserializeSeed, Deserializer, EnumAccess, Error, IgnoredAny, IntoDeserializer, MapAccess,
+    SeqAccess, Visitor,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::osc::Comparable;
+
+/// Element names used to identify an element independent of its position
+const LABEL_ELEMENTS: [&str; 3] = ["Name", "Bezeichnung", "Code"];
+
+type Position = Vec<(String, usize)>;
+
+/// An element or attribute not known to the model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnknownElement {
+    /// Name of the element. Names of attributes are prefixed with `@`.
+    pub name: String,
+    /// Readable path to the parent element
+    pub location: String,
+    /// Line in the original content
+    pub line: usize,
+    parent: Vec<String>,
+    preceding: Vec<String>,
+    content: String,
+}
+
+impl UnknownElement {
+    /// Checks if this element is contained in the given catalogue or form of the `Editor` element
+    pub(crate) fn is_within(&self, element: &str, item: &impl Comparable) -> bool {
+        let guid = item.get_guid();
+        let label = if guid.is_empty() {
+            item.get_name()
+        } else {
+            guid
+        };
+        self.parent
+            .get(2)
+            .is_some_and(|identity| *identity == format!("{element}[{label}]"))
+    }
+}
+
+/// Deserializes the given content and returns all skipped unknown elements
+pub(crate) fn from_str<'de, T: Deserialize<'de>>(
+    s: &'de str,
+) -> Result<(T, Vec<UnknownElement>), String> {
+    let skipped = RefCell::new(vec![]);
+    let mut deserializer = quick_xml::de::Deserializer::from_str(s);
+    let value = T::deserialize(Lossless {
+        inner: &mut deserializer,
+        ctx: Context {
+            skipped: &skipped,
+            path: vec![],
+            key: None,
+        },
+    })
+    .map_err(|err| err.to_string())?;
+
+    let unknown_elements = locate(s, &skipped.into_inner())?;
+    Ok((value, unknown_elements))
+}
+
+/// Inserts unknown elements into serialized content.
+/// Unknown elements of parent elements no longer contained in the content are dropped.
+pub(crate) fn restore(xml: &str, unknown_elements: &[UnknownElement]) -> String {
+    if unknown_elements.is_empty() {
+        return xml.to_string();
+    }
+
+    let Ok(frames) = Frame::read_all(xml) else {
+        return xml.to_string();
+    };
+
+    let mut children_by_identity = HashMap::<usize, HashMap<String, usize>>::new();
+    let mut insertions = vec![];
+
+    for unknown_element in unknown_elements {
+        let Some(parent) =
+            find_by_identity(&frames, &unknown_element.parent, &mut children_by_identity)
+        else {
+            continue;
+        };
+        let frame = &frames[parent];
+
+        if unknown_element.name.starts_with('@') {
+            let offset = if frame.empty {
+                frame.content_start - 2
+            } else {
+                frame.content_start - 1
+            };
+            insertions.push((offset, format!(" {}", unknown_element.content)));
+            continue;
+        }
+
+        if frame.empty {
+            continue;
+        }
+
+        let sibling = unknown_element.preceding.iter().rev().find_map(|name| {
+            frame
+                .children
+                .iter()
+                .rev()
+                .find(|&&child| frames[child].name == *name)
+        });
+
+        match sibling {
+            Some(&sibling) => insertions.push((
+                frames[sibling].end,
+                format!(
+                    "{}{}",
+                    indentation(xml, frames[sibling].start),
+                    unknown_element.content
+                ),
+            )),
+            None => insertions.push((
+                frame.content_start,
+                format!(
+                    "{}{}",
+                    frame
+                        .children
+                        .first()
+                        .map(|&child| indentation(xml, frames[child].start))
+                        .unwrap_or_default(),
+                    unknown_element.content
+                ),
+            )),
+        }
+    }
+
+    insertions.sort_by_key(|(offset, _)| *offset);
+
+    let mut result = String::with_capacity(xml.len());
+    let mut last = 0;
+    for (offset, content) in insertions {
+        result.push_str(&xml[last..offset]);
+        result.push_str(&content);
+        last = offset;
+    }
+    result.push_str(&xml[last..]);
+    result
+}
+
+fn locate(xml: &str, skipped: &[Skipped]) -> Result<Vec<UnknownElement>, String> {
+    if skipped.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let frames = Frame::read_all(xml)?;
+    let mut occurrences = HashMap::<(usize, &str), usize>::new();
+    let mut result = vec![];
+
+    for skipped in skipped {
+        let not_found = || {
+            format!(
+                "Position des unbekannten Elements '{}' kann nicht bestimmt werden",
+                skipped.name
+            )
+        };
+
+        let parent = find_by_position(&frames, &skipped.parent).ok_or_else(not_found)?;
+        let frame = &frames[parent];
+
+        let found = if let Some(attribute) = skipped.name.strip_prefix('@') {
+            raw_attribute(&xml[frame.start..frame.content_start], attribute)
+                .map(|content| (frame.start, content))
+        } else {
+            let occurrence = occurrences
+                .entry((parent, skipped.name.as_str()))
+                .or_default();
+            let element = frame
+                .children
+                .iter()
+                .map(|&child| &frames[child])
+                .filter(|child| child.name == skipped.name)
+                .nth(*occurrence);
+            *occurrence += 1;
+            element.map(|element| (element.start, xml[element.start..element.end].to_string()))
+        };
+
+        let (start, content) = found.ok_or_else(not_found)?;
+
+        result.push(UnknownElement {
+            name: skipped.name.clone(),
+            location: location(&frames, parent),
+            line: xml[..start].matches('\n').count() + 1,
+            parent: identity(&frames, parent),
+            preceding: skipped.preceding.clone(),
+            content,
+        });
+    }
+
+    Ok(result)
+}
+
+fn find_by_position(frames: &[Frame], position: &Position) -> Option<usize> {
+    let mut current = if frames.is_empty() { None } else { Some(0) };
+    for (name, occurrence) in position {
+        current = current.and_then(|current| {
+            frames[current]
+                .children
+                .iter()
+                .find(|&&child| {
+                    frames[child].name == *name && frames[child].occurrence == *occurrence
+                })
+                .copied()
+        });
+    }
+    current
+}
+
+fn find_by_identity(
+    frames: &[Frame],
+    identity: &[String],
+    children_by_identity: &mut HashMap<usize, HashMap<String, usize>>,
+) -> Option<usize> {
+    let (root, identity) = identity.split_first()?;
+    if frames.is_empty() || frames[0].identity() != *root {
+        return None;
+    }
+
+    let mut current = 0;
+    for segment in identity {
+        current = *children_by_identity
+            .entry(current)
+            .or_insert_with(|| {
+                frames[current]
+                    .children
+                    .iter()
+                    .map(|&child| (frames[child].identity(), child))
+                    .collect()
+            })
+            .get(segment)?;
+    }
+    Some(current)
+}
+
+fn identity(frames: &[Frame], index: usize) -> Vec<String> {
+    let mut result = vec![];
+    let mut current = Some(index);
+    while let Some(index) = current {
+        result.push(frames[index].identity());
+        current = frames[index].parent;
+    }
+    result.reverse();
+    result
+}
+
+fn location(frames: &[Frame], index: usize) -> String {
+    let mut result = vec![];
+    let mut current = Some(index);
+    while let Some(index) = current {
+        let frame = &frames[index];
+        if frame.parent.is_some() {
+            result.push(match &frame.label {
+                Some(label) => format!("{} '{}'", frame.name, label),
+                None => frame.name.clone(),
+            });
+        }
+        current = frame.parent;
+    }
+    result.reverse();
+    result.join(" > ")
+}
+
+fn indentation(xml: &str, offset: usize) -> &str {
+    let prefix = &xml[..offset];
+    &prefix[prefix.trim_end().len()..]
+}
+
+fn raw_attribute(start_tag: &str, name: &str) -> Option<String> {
+    let mut reader = Reader::from_str(start_tag);
+    match reader.read_event() {
+        Ok(Event::Start(element) | Event::Empty(element)) => element
+            .attributes()
+            .flatten()
+            .find(|attribute| attribute.key.as_ref() == name.as_bytes())
+            .map(|attribute| {
+                format!(
+                    "{}=\"{}\"",
+                    name,
+                    String::from_utf8_lossy(&attribute.value).replace('"', "&quot;")
+                )
+            }),
+        _ => None,
+    }
+}
+
+/// Element within XML content with its byte offsets
+struct Frame {
+    name: String,
+    parent: Option<usize>,
+    occurrence: usize,
+    start: usize,
+    content_start: usize,
+    end: usize,
+    empty: bool,
+    guid: Option<String>,
+    label: Option<String>,
+    children: Vec<usize>,
+}
+
+impl Frame {
+    fn read_all(xml: &str) -> Result<Vec<Frame>, String> {
+        let mut reader = Reader::from_str(xml);
+        let mut frames: Vec<Frame> = vec![];
+        let mut stack = vec![];
+        let mut text = String::new();
+
+        loop {
+            let start = offset(&reader);
+            let event = reader.read_event().map_err(|err| err.to_string())?;
+            let end = offset(&reader);
+
+            match event {
+                Event::Start(element) => {
+                    let index =
+                        Self::push(&mut frames, &element, stack.last().copied(), start, end);
+                    stack.push(index);
+                    text.clear();
+                }
+                Event::Empty(element) => {
+                    let index =
+                        Self::push(&mut frames, &element, stack.last().copied(), start, end);
+                    frames[index].end = end;
+                    frames[index].empty = true;
+                }
+                Event::Text(content) => {
+                    text.push_str(&content.decode().map_err(|err| err.to_string())?);
+                }
+                Event::GeneralRef(reference) => {
+                    text.push('&');
+                    text.push_str(&reference.decode().map_err(|err| err.to_string())?);
+                    text.push(';');
+                }
+                Event::End(_) => {
+                    let Some(index) = stack.pop() else {
+                        return Err("Ungültiger XML-Inhalt".to_string());
+                    };
+                    frames[index].end = end;
+
+                    if let Some(parent) = frames[index].parent {
+                        let value = unescape(&text).map_or(text.clone(), |value| value.to_string());
+                        if frames[index].name == "GUID" {
+                            frames[parent].guid.get_or_insert(value);
+                        } else if LABEL_ELEMENTS.contains(&frames[index].name.as_str()) {
+                            frames[parent].label.get_or_insert(value);
+                        }
+                    }
+                    text.clear();
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        Ok(frames)
+    }
+
+    fn push(
+        frames: &mut Vec<Frame>,
+        element: &BytesStart,
+        parent: Option<usize>,
+        start: usize,
+        content_start: usize,
+    ) -> usize {
+        let name = String::from_utf8_lossy(element.name().as_ref()).to_string();
+        let index = frames.len();
+        let occurrence = parent.map_or(0, |parent| {
+            frames[parent]
+                .children
+                .iter()
+                .filter(|&&child| frames[child].name == name)
+                .count()
+        });
+        if let Some(parent) = parent {
+            frames[parent].children.push(index);
+        }
+        frames.push(Frame {
+            name,
+            parent,
+            occurrence,
+            start,
+            content_start,
+            end: content_start,
+            empty: false,
+            guid: None,
+            label: None,
+            children: vec![],
+        });
+        index
+    }
+
+    fn identity(&self) -> String {
+        match (&self.guid, &self.label) {
+            (Some(guid), _) => format!("{}[{}]", self.name, guid),
+            (None, Some(label)) => format!("{}[{}]", self.name, label),
+            (None, None) => format!("{}[#{}]", self.name, self.occurrence),
+        }
+    }
+}
+
+fn offset(reader: &Reader<&[u8]>) -> usize {
+    usize::try_from(reader.buffer_position()).unwrap_or(usize::MAX)
+}
+
+/// Unknown element as seen while deserializing, with the position of its parent element
+struct Skipped {
+    parent: Position,
+    name: String,
+    preceding: Vec<String>,
+}
+
+#[derive(Clone)]
+struct Context<'r> {
+    skipped: &'r RefCell<Vec<Skipped>>,
+    path: Position,
+    key: Option<(String, usize)>,
+}
+
+impl<'r> Context<'r> {
+    fn with_key(&self, key: Option<(String, usize)>) -> Self {
+        Context {
+            skipped: self.skipped,
+            path: self.path.clone(),
+            key,
+        }
+    }
+
+    fn nested_path(&self) -> Position {
+        let mut path = self.path.clone();
+        if let Some(key) = &self.key {
+            path.push(key.clone());
+        }
+        path
+    }
+}
+
+/// Deserializer forwarding to the inner deserializer while tracking the current position
+struct Lossless<'r, D> {
+    inner: D,
+    ctx: Context<'r>,
+}
+
+macro_rules! forward_deserialize {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                self.inner.$method(Tracking::new(visitor, self.ctx, None))
+            }
+        )*
+    };
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for Lossless<'_, D> {
+    type Error = D::Error;
+
+    forward_deserialize! {
+        deserialize_any deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32
+        deserialize_i64 deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32
+        deserialize_u64 deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char
+        deserialize_str deserialize_string deserialize_bytes deserialize_byte_buf
+        deserialize_option deserialize_unit deserialize_seq deserialize_map
+        deserialize_identifier deserialize_ignored_any
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.inner
+            .deserialize_unit_struct(name, Tracking::new(visitor, self.ctx, None))
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.inner
+            .deserialize_newtype_struct(name, Tracking::new(visitor, self.ctx, None))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.inner
+            .deserialize_tuple(len, Tracking::new(visitor, self.ctx, None))
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.inner
+            .deserialize_tuple_struct(name, len, Tracking::new(visitor, self.ctx, None))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.inner
+            .deserialize_struct(name, fields, Tracking::new(visitor, self.ctx, Some(fields)))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.inner
+            .deserialize_enum(name, variants, Tracking::new(visitor, self.ctx, None))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+/// Visitor wrapping nested deserializers, sequences and maps
+struct Tracking<'r, V> {
+    visitor: V,
+    ctx: Context<'r>,
+    fields: Option<&'static [&'static str]>,
+}
+
+impl<'r, V> Tracking<'r, V> {
+    fn new(visitor: V, ctx: Context<'r>, fields: Option<&'static [&'static str]>) -> Self {
+        Tracking {
+            visitor,
+            ctx,
+            fields,
+        }
+    }
+}
+
+macro_rules! forward_visit {
+    ($($method:ident($ty:ty))*) => {
+        $(
+            fn $method<E: Error>(self, v: $ty) -> Result<Self::Value, E> {
+                self.visitor.$method(v)
+            }
+        )*
+    };
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for Tracking<'_, V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        self.visitor.expecting(formatter)
+    }
+
+    forward_visit! {
+        visit_bool(bool) visit_i8(i8) visit_i16(i16) visit_i32(i32) visit_i64(i64)
+        visit_i128(i128) visit_u8(u8) visit_u16(u16) visit_u32(u32) visit_u64(u64)
+        visit_u128(u128) visit_f32(f32) visit_f64(f64) visit_char(char) visit_str(&str)
+        visit_borrowed_str(&'de str) visit_string(String) visit_bytes(&[u8])
+        visit_borrowed_bytes(&'de [u8]) visit_byte_buf(Vec<u8>)
+    }
+
+    fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+        self.visitor.visit_none()
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+        self.visitor.visit_unit()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.visitor.visit_some(Lossless {
+            inner: deserializer,
+            ctx: self.ctx,
+        })
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        self.visitor.visit_newtype_struct(Lossless {
+            inner: deserializer,
+            ctx: self.ctx,
+        })
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        self.visitor.visit_seq(LosslessSeq {
+            inner: seq,
+            ctx: self.ctx,
+            index: 0,
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        self.visitor.visit_map(LosslessMap {
+            inner: map,
+            path: self.ctx.nested_path(),
+            skipped: self.ctx.skipped,
+            fields: self.fields,
+            preceding: vec![],
+            key: None,
+        })
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        self.visitor.visit_enum(data)
+    }
+}
+
+struct LosslessSeed<'r, T> {
+    seed: T,
+    ctx: Context<'r>,
+}
+
+impl<'de, T: DeserializeSeed<'de>> DeserializeSeed<'de> for LosslessSeed<'_, T> {
+    type Value = T::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.seed.deserialize(Lossless {
+            inner: deserializer,
+            ctx: self.ctx,
+        })
+    }
+}
+
+struct LosslessSeq<'r, A> {
+    inner: A,
+    ctx: Context<'r>,
+    index: usize,
+}
+
+impl<'de, A: SeqAccess<'de>> SeqAccess<'de> for LosslessSeq<'_, A> {
+    type Error = A::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        let key = self
+            .ctx
+            .key
+            .as_ref()
+            .map(|(name, _)| (name.clone(), self.index));
+        self.index += 1;
+        self.inner.next_element_seed(LosslessSeed {
+            seed,
+            ctx: self.ctx.with_key(key),
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+/// Map access skipping and recording all keys not contained in the known fields
+struct LosslessMap<'r, A> {
+    inner: A,
+    path: Position,
+    skipped: &'r RefCell<Vec<Skipped>>,
+    fields: Option<&'static [&'static str]>,
+    preceding: Vec<String>,
+    key: Option<String>,
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for LosslessMap<'_, A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        while let Some(key) = self.inner.next_key::<String>()? {
+            if self
+                .fields
+                .is_none_or(|fields| fields.contains(&key.as_str()))
+            {
+                if !key.starts_with('@') {
+                    self.preceding.push(key.clone());
+                }
+                self.key = Some(key.clone());
+                let deserializer: StringDeserializer<A::Error> = key.into_deserializer();
+                return seed.deserialize(deserializer).map(Some);
+            }
+
+            self.inner.next_value::<IgnoredAny>()?;
+            self.skipped.borrow_mut().push(Skipped {
+                parent: self.path.clone(),
+                name: key,
+                preceding: self.preceding.clone(),
+            });
+        }
+        Ok(None)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let key = self.key.take().map(|key| (key, 0));
+        self.inner.next_value_seed(LosslessSeed {
+            seed,
+            ctx: Context {
+                skipped: self.skipped,
+                path: self.path.clone(),
+                key,
+            },
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use quick_xml::se::Serializer;
+    use serde::Serialize;
+
+    use crate::osc::onkostar_editor::OnkostarEditor;
+
+    fn content_with_unknown_elements() -> String {
+        include_str!("../../tests/test.osc")
+            .replace(
+                "<Hinweis>Wählen Sie hier den Wert aus.</Hinweis>",
+                "<Hinweis>Wählen Sie hier den Wert aus.</Hinweis>\n          <NeueEigenschaft>42</NeueEigenschaft>",
+            )
+            .replace(
+                "<Entry parentRefId=\"10395\">",
+                "<Entry parentRefId=\"10395\" neuesAttribut=\"1\">",
+            )
+    }
+
+    fn serialize(onkostar_editor: &OnkostarEditor) -> String {
+        let mut buf = String::new();
+        let mut serializer = Serializer::new(&mut buf);
+        serializer.indent(' ', 2);
+        onkostar_editor.serialize(serializer).unwrap();
+        onkostar_editor.restore_unknown_elements(&buf)
+    }
+
+    #[test]
+    fn should_fail_on_unknown_elements_without_lossless_mode() {
+        let onkostar_editor = OnkostarEditor::from_str(&content_with_unknown_elements());
+
+        assert!(onkostar_editor.is_err());
+    }
+
+    #[test]
+    fn should_keep_unknown_elements() {
+        let onkostar_editor =
+            OnkostarEditor::from_str_lossless(&content_with_unknown_elements()).unwrap();
+
+        assert_eq!(onkostar_editor.unknown_elements.len(), 2);
+        assert_eq!(onkostar_editor.unknown_elements[0].name, "@neuesAttribut");
+        assert_eq!(onkostar_editor.unknown_elements[1].name, "NeueEigenschaft");
+        assert_eq!(
+            onkostar_editor.unknown_elements[1].location,
+            "Editor > DataForm 'Hauptformular' > Entries > Entry 'Auswahl'"
+        );
+        assert_eq!(onkostar_editor.unknown_elements[1].line, 593);
+    }
+
+    #[test]
+    fn should_not_find_unknown_elements_in_known_content() {
+        let onkostar_editor =
+            OnkostarEditor::from_str_lossless(include_str!("../../tests/test.osc")).unwrap();
+
+        assert!(onkostar_editor.unknown_elements.is_empty());
+    }
+
+    #[test]
+    fn should_restore_unknown_elements() {
+        let onkostar_editor =
+            OnkostarEditor::from_str_lossless(&content_with_unknown_elements()).unwrap();
+
+        let actual = serialize(&onkostar_editor);
+
+        assert!(actual.contains("<Entry parentRefId=\"10395\" neuesAttribut=\"1\">"));
+        assert!(actual.contains(
+            "<Hinweis>Wählen Sie hier den Wert aus.</Hinweis>\n          <NeueEigenschaft>42</NeueEigenschaft>"
+        ));
+    }
+
+    #[test]
+    fn should_restore_unknown_elements_after_sorting() {
+        let mut onkostar_editor =
+            OnkostarEditor::from_str_lossless(&content_with_unknown_elements()).unwrap();
+        onkostar_editor.sorted();
+
+        let actual = OnkostarEditor::from_str_lossless(&serialize(&onkostar_editor)).unwrap();
+
+        assert_eq!(actual.unknown_elements.len(), 2);
+        assert_eq!(
+            actual.unknown_elements[1].location,
+            "Editor > DataForm 'Hauptformular' > Entries > Entry 'Auswahl'"
+        );
+    }
+
+    #[test]
+    fn should_drop_unknown_elements_of_removed_content() {
+        let mut onkostar_editor =
+            OnkostarEditor::from_str_lossless(&content_with_unknown_elements()).unwrap();
+        onkostar_editor.filter_by_name_contains("Unterformular");
+
+        let actual = serialize(&onkostar_editor);
+
+        assert!(!actual.contains("NeueEigenschaft"));
+        assert!(OnkostarEditor::from_str(&actual).is_ok());
+    }
+}
@@ -0,0 +1,325 @@
+/*
+ * This file is part of osc-variant
+ *
+ * Copyright (C) 2026 the original author or authors.
+ *
+ * This program is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation; either version 2 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along
+ * with this program; if not, write to the Free Software Foundation, Inc.,
+ * 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
+ */
+
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+use serde::Serialize;
+
+use crate::osc::Comparable;
+use crate::osc::lossless::UnknownElement;
+
+/// Handling of items contained in both files with equal revision but different content
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConflictPolicy {
+    PreferLeft,
+    PreferRight,
+    Fail,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentType {
+    PropertyCatalogue,
+    DataCatalogue,
+    DataForm,
+    Unterformular,
+}
+
+impl Display for ContentType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ContentType::PropertyCatalogue => "Merkmalskatalog",
+                ContentType::DataCatalogue => "Datenkatalog",
+                ContentType::DataForm => "Formular",
+                ContentType::Unterformular => "Unterformular",
+            }
+        )
+    }
+}
+
+/// Item contained in both files with equal revision but different content
+#[derive(Debug)]
+pub struct MergeConflict {
+    pub content_type: ContentType,
+    pub name: String,
+    pub revision: u16,
+}
+
+impl Display for MergeConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} '{}' in Revision '{}' mit unterschiedlichem Inhalt",
+            self.content_type, self.name, self.revision
+        )
+    }
+}
+
+/// Handling of an item of the right side
+pub(crate) enum MergeAction {
+    Add,
+    Replace(usize),
+    Keep,
+}
+
+/// Determines the handling of all items of `right` without changing `left`. Items contained in
+/// both with the same GUID or name are replaced if the revision of the item in `right` is higher.
+pub(crate) fn plan_merge<T: Comparable>(
+    left: &[T],
+    right: &[T],
+    content_type: ContentType,
+    policy: ConflictPolicy,
+    conflicts: &mut Vec<MergeConflict>,
+) -> Vec<MergeAction> {
+    right
+        .iter()
+        .map(|item| {
+            let existing = left.iter().position(|existing| {
+                (!item.get_guid().is_empty() && existing.get_guid() == item.get_guid())
+                    || existing.get_name() == item.get_name()
+            });
+
+            let Some(index) = existing else {
+                return MergeAction::Add;
+            };
+
+            match item.get_revision().cmp(&left[index].get_revision()) {
+                Ordering::Greater => MergeAction::Replace(index),
+                Ordering::Equal if item.get_hash() != left[index].get_hash() => {
+                    conflicts.push(MergeConflict {
+                        content_type,
+                        name: item.get_name(),
+                        revision: item.get_revision(),
+                    });
+                    if policy == ConflictPolicy::PreferRight {
+                        MergeAction::Replace(index)
+                    } else {
+                        MergeAction::Keep
+                    }
+                }
+                _ => MergeAction::Keep,
+            }
+        })
+        .collect()
+}
+
+/// Adds or replaces items of `left` as planned. Unknown elements of replaced items are
+/// removed and unknown elements of items taken from `right` are added.
+pub(crate) fn apply_merge<T: Comparable>(
+    left: &mut Vec<T>,
+    right: Vec<T>,
+    actions: Vec<MergeAction>,
+    element: &str,
+    unknown_elements: &mut Vec<UnknownElement>,
+    right_unknown_elements: &[UnknownElement],
+) {
+    for (item, action) in right.into_iter().zip(actions) {
+        match action {
+            MergeAction::Add => {}
+            MergeAction::Replace(index) => {
+                unknown_elements.retain(|unknown| !unknown.is_within(element, &left[index]));
+            }
+            MergeAction::Keep => continue,
+        }
+
+        for unknown_element in right_unknown_elements
+            .iter()
+            .filter(|unknown| unknown.is_within(element, &item))
+        {
+            if !unknown_elements.contains(unknown_element) {
+                unknown_elements.push(unknown_element.clone());
+            }
+        }
+
+        match action {
+            MergeAction::Replace(index) => left[index] = item,
+            _ => left.push(item),
+        }
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::osc::merge::{ConflictPolicy, ContentType};
+    use crate::osc::onkostar_editor::OnkostarEditor;
+    use crate::osc::{Comparable, Named, Revisioned};
+
+    fn left() -> OnkostarEditor {
+        OnkostarEditor::from_str(include_str!("../../tests/test.osc")).unwrap()
+    }
+
+    fn right(revision: &str, description: &str) -> OnkostarEditor {
+        OnkostarEditor::from_str(
+            &include_str!("../../tests/test.osc")
+                .replace("<Revision>166</Revision>", revision)
+                .replace("<Description>Test Hauptformular</Description>", description),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn should_keep_higher_revision() {
+        let mut onkostar_editor = left();
+
+        let conflicts = onkostar_editor
+            .merge(
+                right("<Revision>167</Revision>", "<Description>Neu</Description>"),
+                ConflictPolicy::Fail,
+            )
+            .unwrap();
+
+        assert!(conflicts.is_empty());
+        assert_eq!(onkostar_editor.editor.data_form.len(), 1);
+        assert_eq!(onkostar_editor.editor.data_form[0].get_revision(), 167);
+    }
+
+    #[test]
+    fn should_keep_left_item_with_higher_revision() {
+        let mut onkostar_editor =
+            right("<Revision>167</Revision>", "<Description>Neu</Description>");
+
+        let conflicts = onkostar_editor.merge(left(), ConflictPolicy::Fail).unwrap();
+
+        assert!(conflicts.is_empty());
+        assert_eq!(onkostar_editor.editor.data_form[0].get_revision(), 167);
+    }
+
+    #[test]
+    fn should_add_items_not_contained() {
+        let mut onkostar_editor = left();
+        onkostar_editor.editor.data_form.clear();
+
+        let conflicts = onkostar_editor.merge(left(), ConflictPolicy::Fail).unwrap();
+
+        assert!(conflicts.is_empty());
+        assert_eq!(
+            onkostar_editor.editor.data_form[0].get_name(),
+            "Hauptformular"
+        );
+    }
+
+    #[test]
+    fn should_fail_on_conflict() {
+        let mut onkostar_editor = left();
+
+        let conflicts = onkostar_editor
+            .merge(
+                right("<Revision>166</Revision>", "<Description>Neu</Description>"),
+                ConflictPolicy::Fail,
+            )
+            .unwrap_err();
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].content_type, ContentType::DataForm);
+        assert_eq!(conflicts[0].name, "Hauptformular");
+    }
+
+    #[test]
+    fn should_resolve_conflict_with_policy() {
+        let mut prefer_left = left();
+        let conflicts = prefer_left
+            .merge(
+                right("<Revision>166</Revision>", "<Description>Neu</Description>"),
+                ConflictPolicy::PreferLeft,
+            )
+            .unwrap();
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(
+            prefer_left.editor.data_form[0].get_hash(),
+            left().editor.data_form[0].get_hash()
+        );
+
+        let mut prefer_right = left();
+        let conflicts = prefer_right
+            .merge(
+                right("<Revision>166</Revision>", "<Description>Neu</Description>"),
+                ConflictPolicy::PreferRight,
+            )
+            .unwrap();
+
+        assert_eq!(conflicts.len(), 1);
+        assert_ne!(
+            prefer_right.editor.data_form[0].get_hash(),
+            left().editor.data_form[0].get_hash()
+        );
+    }
+
+    #[test]
+    fn should_not_change_left_side_on_failed_merge() {
+        let mut onkostar_editor = left();
+        onkostar_editor.editor.property_catalogue.clear();
+
+        let result = onkostar_editor.merge(
+            right("<Revision>166</Revision>", "<Description>Neu</Description>"),
+            ConflictPolicy::Fail,
+        );
+
+        assert!(result.is_err());
+        assert!(onkostar_editor.editor.property_catalogue.is_empty());
+        assert_eq!(
+            onkostar_editor.editor.data_form[0].get_hash(),
+            left().editor.data_form[0].get_hash()
+        );
+    }
+
+    #[test]
+    fn should_only_keep_unknown_elements_of_items_taken_from_right_side() {
+        let right_with_unknown_element = |revision: &str| {
+            OnkostarEditor::from_str_lossless(
+                &include_str!("../../tests/test.osc")
+                    .replace("<Revision>166</Revision>", revision)
+                    .replace(
+                        "<Description>Test Hauptformular</Description>",
+                        "<Description>Neu</Description><NeueEigenschaft>1</NeueEigenschaft>",
+                    ),
+            )
+            .unwrap()
+        };
+
+        let mut onkostar_editor = left();
+        onkostar_editor
+            .merge(
+                right_with_unknown_element("<Revision>165</Revision>"),
+                ConflictPolicy::Fail,
+            )
+            .unwrap();
+
+        assert!(onkostar_editor.unknown_elements.is_empty());
+
+        let mut onkostar_editor = left();
+        onkostar_editor
+            .merge(
+                right_with_unknown_element("<Revision>167</Revision>"),
+                ConflictPolicy::Fail,
+            )
+            .unwrap();
+
+        assert_eq!(onkostar_editor.unknown_elements.len(), 1);
+        assert_eq!(onkostar_editor.unknown_elements[0].name, "NeueEigenschaft");
+    }
+}
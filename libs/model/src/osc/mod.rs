@@ -18,15 +18,17 @@
  * 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
  */
 
-use crate::osc::requirements::Requires;
 use serde::{Deserialize, Serialize};
-use std::cmp::Ordering;
 use std::collections::hash_map::DefaultHasher;
 use std::fmt::Debug;
 use std::hash::{Hash, Hasher};
 
 pub mod data_catalogue;
+mod dependencies;
+pub mod diff;
 pub mod form;
+pub mod lossless;
+pub mod merge;
 pub mod onkostar_editor;
 pub mod other;
 pub mod property_catalogue;
@@ -41,6 +43,30 @@ pub struct Script {
     valid: bool,
 }
 
+impl Script {
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// Creates a valid script with the given, not yet escaped code
+    pub fn new(code: &str) -> Self {
+        Script {
+            code: escape_script(code),
+            valid: true,
+        }
+    }
+}
+
+/// Escapes line breaks in script code as expected by Onkostar
+pub fn escape_script(script: &str) -> String {
+    script.replace('\n', "&#10;")
+}
+
+/// Restores line breaks in script code escaped by `escape_script`
+pub fn unescape_script(script: &str) -> String {
+    script.replace("&#10;", "\n")
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct PlausibilityRule<T> {
@@ -203,6 +229,21 @@ pub struct Filter {
     ref_entries: Option<RefEntries>,
 }
 
+impl Filter {
+    /// Names of the form fields used in the filter condition
+    pub fn ref_entries(&self) -> &[String] {
+        self.ref_entries
+            .as_ref()
+            .and_then(|ref_entries| ref_entries.ref_entry.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Returns true if the filter condition is always false, as set by hiding a form field
+    pub fn is_always_false(&self) -> bool {
+        self.condition.trim() == "false"
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct RefEntries {
@@ -339,22 +380,25 @@ pub trait Comparable: Debug + Named + Revisioned {
         format!("{self:?}").hash(&mut h);
         format!("{:x}", h.finish())
     }
-    fn compare_by_requirement(_: &Self, _: &Self) -> Ordering
-    where
-        Self: Requires,
-    {
-        Ordering::Equal
-    }
 }
 
 pub trait UpdatableEntry: Named {
     fn update_referenced_data_form(&mut self, value: String);
     fn update_anzeige(&mut self, value: String);
     fn update_anzeige_auswahl(&mut self, value: String);
-    fn update_scripts_code(&mut self, value: String);
-    fn update_default_value(&mut self, value: String);
-    fn hide(&mut self);
-    fn remove_filter(&mut self);
+    // The following updates return whether the value has been changed
+    fn update_scripts_code(&mut self, value: String) -> bool;
+    fn update_default_value(&mut self, value: String) -> bool;
+    fn hide(&mut self) -> bool;
+    fn remove_filter(&mut self) -> bool;
+    fn update_mandatory(&mut self, value: bool) -> bool;
+    fn update_read_only(&mut self, value: bool) -> bool;
+    fn update_active(&mut self, value: bool) -> bool;
+    fn update_description(&mut self, value: String) -> bool;
+    fn update_hinweis(&mut self, value: String) -> bool;
+    fn update_position(&mut self, value: String) -> bool;
+    fn update_element_parent(&mut self, value: String) -> bool;
+    fn increase_revision(&mut self);
 }
 
 pub trait FolderContained {
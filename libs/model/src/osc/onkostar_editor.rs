@@ -18,15 +18,22 @@
  * 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
  */
 
+use std::collections::{BTreeSet, HashMap};
 use std::fmt::Debug;
+use std::mem::discriminant;
 use std::str::FromStr;
 
 use crate::osc::data_catalogue::DataCatalogue;
+use crate::osc::dependencies::DependencyGraph;
 use crate::osc::form::{DataFormType, Form, UnterformularType};
+use crate::osc::lossless;
+use crate::osc::lossless::UnknownElement;
+use crate::osc::merge::{ConflictPolicy, ContentType, MergeConflict, apply_merge, plan_merge};
 use crate::osc::other::{Ablaufschema, Akte, RecordLinkage, Rskript, SidGuid};
 use crate::osc::property_catalogue::PropertyCatalogue;
-use crate::osc::{Comparable, FolderContained, Named, Sortable};
-use crate::profile::{Profile, ProfileApplicable};
+use crate::osc::requirements::{Requirement, Requires};
+use crate::osc::{FolderContained, Named, Sortable};
+use crate::profile::{AppliedRules, Profile, ProfileApplicable, UnmatchedRule};
 
 use quick_xml::de::from_str;
 use serde::{Deserialize, Serialize};
@@ -38,9 +45,109 @@ pub struct OnkostarEditor {
     pub info_xml: InfoXML,
     #[serde(rename = "Editor")]
     pub editor: Editor,
+    /// Unknown elements kept by lossless deserialization
+    #[serde(skip)]
+    pub unknown_elements: Vec<UnknownElement>,
 }
 
 impl OnkostarEditor {
+    /// Deserializes OSC content like `from_str()`, but keeps unknown elements instead of failing
+    pub fn from_str_lossless(s: &str) -> Result<Self, String> {
+        check_supported_content(s)?;
+
+        let (mut result, unknown_elements) = lossless::from_str::<OnkostarEditor>(s)?;
+        result.unknown_elements = unknown_elements;
+        Ok(result)
+    }
+
+    /// Writes kept unknown elements back into the serialized content
+    pub fn restore_unknown_elements(&self, xml: &str) -> String {
+        lossless::restore(xml, &self.unknown_elements)
+    }
+
+    /// Adds all catalogues and forms of `other`. Items contained in both with the same GUID or
+    /// name are replaced if the revision in `other` is higher.
+    /// Items with equal revision but different content are handled by the given policy and
+    /// returned as conflicts. If the policy is `ConflictPolicy::Fail`, all conflicts are returned
+    /// as error and this file is not changed.
+    /// Unknown elements of `other` are only kept for items taken from `other`.
+    pub fn merge(
+        &mut self,
+        other: OnkostarEditor,
+        policy: ConflictPolicy,
+    ) -> Result<Vec<MergeConflict>, Vec<MergeConflict>> {
+        let mut conflicts = vec![];
+
+        let property_catalogues = plan_merge(
+            &self.editor.property_catalogue,
+            &other.editor.property_catalogue,
+            ContentType::PropertyCatalogue,
+            policy,
+            &mut conflicts,
+        );
+        let data_catalogues = plan_merge(
+            &self.editor.data_catalogue,
+            &other.editor.data_catalogue,
+            ContentType::DataCatalogue,
+            policy,
+            &mut conflicts,
+        );
+        let unterformulare = plan_merge(
+            &self.editor.unterformular,
+            &other.editor.unterformular,
+            ContentType::Unterformular,
+            policy,
+            &mut conflicts,
+        );
+        let data_forms = plan_merge(
+            &self.editor.data_form,
+            &other.editor.data_form,
+            ContentType::DataForm,
+            policy,
+            &mut conflicts,
+        );
+
+        // Keep this file unchanged if the merge fails
+        if policy == ConflictPolicy::Fail && !conflicts.is_empty() {
+            return Err(conflicts);
+        }
+
+        apply_merge(
+            &mut self.editor.property_catalogue,
+            other.editor.property_catalogue,
+            property_catalogues,
+            "PropertyCatalogue",
+            &mut self.unknown_elements,
+            &other.unknown_elements,
+        );
+        apply_merge(
+            &mut self.editor.data_catalogue,
+            other.editor.data_catalogue,
+            data_catalogues,
+            "DataCatalogue",
+            &mut self.unknown_elements,
+            &other.unknown_elements,
+        );
+        apply_merge(
+            &mut self.editor.unterformular,
+            other.editor.unterformular,
+            unterformulare,
+            "Unterformular",
+            &mut self.unknown_elements,
+            &other.unknown_elements,
+        );
+        apply_merge(
+            &mut self.editor.data_form,
+            other.editor.data_form,
+            data_forms,
+            "DataForm",
+            &mut self.unknown_elements,
+            &other.unknown_elements,
+        );
+
+        Ok(conflicts)
+    }
+
     pub fn find_property_catalogue(&self, name: &str) -> Option<&PropertyCatalogue> {
         match self
             .editor
@@ -93,21 +200,24 @@ impl OnkostarEditor {
         }
     }
 
-    pub fn apply_profile(&mut self, profile: &Profile) {
+    /// Applies the profile and returns all profile rules without a matching form or form entry
+    pub fn apply_profile(&mut self, profile: &Profile) -> Vec<UnmatchedRule> {
+        let mut applied = AppliedRules::default();
         self.editor
             .data_form
             .iter_mut()
             .filter(|data_form| !data_form.is_system_library_content())
             .for_each(|data_form| {
-                data_form.apply_profile(profile);
+                data_form.apply_profile(profile, &mut applied);
             });
         self.editor
             .unterformular
             .iter_mut()
             .filter(|data_form| !data_form.is_system_library_content())
             .for_each(|data_form| {
-                data_form.apply_profile(profile);
+                data_form.apply_profile(profile, &mut applied);
             });
+        applied.unmatched(profile)
     }
 
     pub fn filter_by_name_contains(&mut self, name: &str) {
@@ -125,7 +235,9 @@ impl OnkostarEditor {
             .retain(|e| e.get_name().contains(name));
     }
 
-    pub fn sorted(&mut self) {
+    /// Sorts catalogues by name and forms by their requirements, required forms first.
+    /// Returns the names of forms requiring each other, these cannot be imported in one pass.
+    pub fn sorted(&mut self) -> Vec<Vec<String>> {
         self.editor
             .property_catalogue
             .sort_unstable_by_key(Sortable::sorting_key);
@@ -142,29 +254,203 @@ impl OnkostarEditor {
             item.sorted();
         });
 
-        /*self.editor
-        .data_form
-        .sort_unstable_by_key(|e| e.sorting_key());*/
+        let cycles = self.sort_forms_by_requirements();
 
-        self.editor
-            .data_form
-            .sort_unstable_by(Form::compare_by_requirement);
+        self.editor.unterformular.iter_mut().for_each(|item| {
+            item.sorted();
+        });
 
         self.editor.data_form.iter_mut().for_each(|item| {
             item.sorted();
         });
 
-        /*self.editor
-        .unterformular
-        .sort_unstable_by_key(|e| e.sorting_key());*/
+        cycles
+    }
+
+    /// Sorts forms by their requirements, required forms first, without changing form contents.
+    /// Returns the names of forms requiring each other, these cannot be imported in one pass.
+    pub fn sort_forms_by_requirements(&mut self) -> Vec<Vec<String>> {
+        let graph = self.dependency_graph();
+        let unterformular_count = self.editor.unterformular.len();
+        let form_count = unterformular_count + self.editor.data_form.len();
+
+        let unterformular_order = graph.sorted(&(0..unterformular_count).collect::<Vec<_>>());
+        reorder(&mut self.editor.unterformular, &unterformular_order);
+
+        let data_form_order = graph
+            .sorted(&(unterformular_count..form_count).collect::<Vec<_>>())
+            .into_iter()
+            .map(|index| index - unterformular_count)
+            .collect::<Vec<_>>();
+        reorder(&mut self.editor.data_form, &data_form_order);
 
+        graph.cycles()
+    }
+
+    /// Retains the given forms and all contents required by them, directly or indirectly.
+    /// All other contents are removed. Returns the required contents not contained.
+    pub fn extract(&mut self, form_names: &[String]) -> Result<Vec<Requirement<'static>>, String> {
+        let mut queue = vec![];
+        for name in form_names {
+            if let Some(form) = self.find_data_form(name) {
+                queue.push(Requirement::DataFormReference(form));
+            } else if let Some(form) = self.find_unterformular(name) {
+                queue.push(Requirement::UnterformularReference(form));
+            } else {
+                return Err(format!("Formular '{name}' ist nicht enthalten"));
+            }
+        }
+
+        let mut property_catalogues = BTreeSet::new();
+        let mut data_catalogues = BTreeSet::new();
+        let mut data_forms = BTreeSet::new();
+        let mut unterformulare = BTreeSet::new();
+        let mut external = vec![];
+
+        while let Some(requirement) = queue.pop() {
+            match requirement {
+                Requirement::PropertyCatalogue(item) => {
+                    property_catalogues.insert(item.get_name());
+                }
+                Requirement::DataCatalogue(item) => {
+                    if data_catalogues.insert(item.get_name()) {
+                        queue.append(&mut item.get_required_entries(self));
+                    }
+                }
+                Requirement::DataFormReference(item) | Requirement::DataFormSubform(item) => {
+                    if data_forms.insert(item.get_name()) {
+                        queue.append(&mut item.get_required_entries(self));
+                    }
+                }
+                Requirement::UnterformularReference(item)
+                | Requirement::UnterformularSubform(item) => {
+                    if unterformulare.insert(item.get_name()) {
+                        queue.append(&mut item.get_required_entries(self));
+                    }
+                }
+                Requirement::ExternalPropertyCatalogue(name) => {
+                    external.push(Requirement::ExternalPropertyCatalogue(name));
+                }
+                Requirement::ExternalDataCatalogue(name) => {
+                    external.push(Requirement::ExternalDataCatalogue(name));
+                }
+                Requirement::ExternalDataFormReference(name) => {
+                    external.push(Requirement::ExternalDataFormReference(name));
+                }
+                Requirement::ExternalUnterformularReference(name) => {
+                    external.push(Requirement::ExternalUnterformularReference(name));
+                }
+                Requirement::ExternalDataFormSubform(name) => {
+                    external.push(Requirement::ExternalDataFormSubform(name));
+                }
+                Requirement::ExternalUnterformularSubform(name) => {
+                    external.push(Requirement::ExternalUnterformularSubform(name));
+                }
+            }
+        }
+
+        external.sort_by_key(Requirement::sorting_key);
+        external.dedup_by(|a, b| {
+            discriminant(a) == discriminant(b) && a.sorting_key() == b.sorting_key()
+        });
+
+        self.editor
+            .property_catalogue
+            .retain(|item| property_catalogues.contains(&item.get_name()));
+        self.editor
+            .data_catalogue
+            .retain(|item| data_catalogues.contains(&item.get_name()));
+        self.editor
+            .data_form
+            .retain(|item| data_forms.contains(&item.get_name()));
         self.editor
             .unterformular
-            .sort_unstable_by(Form::compare_by_requirement);
+            .retain(|item| unterformulare.contains(&item.get_name()));
 
-        self.editor.unterformular.iter_mut().for_each(|item| {
-            item.sorted();
-        });
+        self.editor.ablaufschema = None;
+        self.editor.akte = None;
+        self.editor.record_linkage = None;
+        self.editor.rskript = None;
+        self.editor.formulare_loeschen = None;
+        self.editor.formulare_deaktivieren = None;
+
+        Ok(external)
+    }
+
+    /// Graph of requirements between all forms, system library content is not considered
+    fn dependency_graph(&self) -> DependencyGraph {
+        let offset = self.editor.unterformular.len();
+
+        let mut unterformular_indices = HashMap::new();
+        for (index, form) in self.editor.unterformular.iter().enumerate() {
+            unterformular_indices
+                .entry(form.get_name())
+                .or_insert(index);
+        }
+        let mut data_form_indices = HashMap::new();
+        for (index, form) in self.editor.data_form.iter().enumerate() {
+            data_form_indices
+                .entry(form.get_name())
+                .or_insert(index + offset);
+        }
+
+        let system_library_content = self
+            .editor
+            .unterformular
+            .iter()
+            .map(FolderContained::is_system_library_content)
+            .chain(
+                self.editor
+                    .data_form
+                    .iter()
+                    .map(FolderContained::is_system_library_content),
+            )
+            .collect::<Vec<_>>();
+
+        let requirements = self
+            .editor
+            .unterformular
+            .iter()
+            .map(|form| form.get_required_entries(self))
+            .chain(
+                self.editor
+                    .data_form
+                    .iter()
+                    .map(|form| form.get_required_entries(self)),
+            )
+            .collect::<Vec<_>>();
+
+        let mut graph = DependencyGraph::new(
+            self.editor
+                .unterformular
+                .iter()
+                .map(Named::get_name)
+                .chain(self.editor.data_form.iter().map(Named::get_name))
+                .collect(),
+        );
+
+        for (form, requirements) in requirements.iter().enumerate() {
+            for requirement in requirements {
+                let required = match requirement {
+                    Requirement::DataFormReference(item) | Requirement::DataFormSubform(item) => {
+                        data_form_indices.get(&item.get_name())
+                    }
+                    Requirement::UnterformularReference(item)
+                    | Requirement::UnterformularSubform(item) => {
+                        unterformular_indices.get(&item.get_name())
+                    }
+                    _ => None,
+                };
+                if let Some(&required) = required
+                    && !system_library_content[form]
+                    && !system_library_content[required]
+                {
+                    graph.add_requirement(form, required);
+                }
+            }
+        }
+
+        graph
     }
 
     pub fn strip_system_library_content(&mut self) {
@@ -190,15 +476,7 @@ impl FromStr for OnkostarEditor {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if !s
-            .matches("<AnalyseBereichEditor>")
-            .collect::<String>()
-            .is_empty()
-        {
-            return Err(
-                "Datei mit Inhalt 'AnalyseBereichEditor' kann nicht verwendet werden".into(),
-            );
-        }
+        check_supported_content(s)?;
 
         match from_str::<OnkostarEditor>(s) {
             Ok(profile) => Ok(profile),
@@ -207,6 +485,28 @@ impl FromStr for OnkostarEditor {
     }
 }
 
+fn reorder<T>(items: &mut Vec<T>, order: &[usize]) {
+    let mut taken = std::mem::take(items)
+        .into_iter()
+        .map(Some)
+        .collect::<Vec<_>>();
+    *items = order
+        .iter()
+        .filter_map(|&index| taken[index].take())
+        .collect();
+}
+
+fn check_supported_content(s: &str) -> Result<(), String> {
+    if !s
+        .matches("<AnalyseBereichEditor>")
+        .collect::<String>()
+        .is_empty()
+    {
+        return Err("Datei mit Inhalt 'AnalyseBereichEditor' kann nicht verwendet werden".into());
+    }
+    Ok(())
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct InfoXML {
@@ -249,3 +549,76 @@ pub struct Editor {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub formulare_deaktivieren: Option<Vec<SidGuid>>,
 }
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::osc::Named;
+    use crate::osc::onkostar_editor::OnkostarEditor;
+    use crate::osc::requirements::Requirement;
+
+    fn names(items: &[impl Named]) -> Vec<String> {
+        items.iter().map(Named::get_name).collect()
+    }
+
+    #[test]
+    fn should_extract_form_with_required_contents() {
+        let mut onkostar_editor =
+            OnkostarEditor::from_str(include_str!("../../tests/test.osc")).unwrap();
+
+        let external = onkostar_editor
+            .extract(&["Hauptformular".to_string()])
+            .unwrap();
+
+        assert_eq!(
+            names(&onkostar_editor.editor.data_form),
+            vec!["Hauptformular"]
+        );
+        assert_eq!(
+            names(&onkostar_editor.editor.unterformular),
+            vec!["Unterformular"]
+        );
+        assert_eq!(
+            names(&onkostar_editor.editor.data_catalogue),
+            vec!["Hauptformulardaten", "Unterformulardaten"]
+        );
+        assert_eq!(external.len(), 1);
+        assert!(
+            matches!(&external[0], Requirement::ExternalDataFormReference(name) if name == "Anderes Formular")
+        );
+    }
+
+    #[test]
+    fn should_extract_subform_without_requiring_forms() {
+        let mut onkostar_editor =
+            OnkostarEditor::from_str(include_str!("../../tests/test.osc")).unwrap();
+
+        let external = onkostar_editor
+            .extract(&["Unterformular".to_string()])
+            .unwrap();
+
+        assert!(onkostar_editor.editor.data_form.is_empty());
+        assert_eq!(
+            names(&onkostar_editor.editor.unterformular),
+            vec!["Unterformular"]
+        );
+        assert_eq!(
+            names(&onkostar_editor.editor.data_catalogue),
+            vec!["Unterformulardaten"]
+        );
+        assert!(external.is_empty());
+    }
+
+    #[test]
+    fn should_not_extract_unknown_form() {
+        let mut onkostar_editor =
+            OnkostarEditor::from_str(include_str!("../../tests/test.osc")).unwrap();
+
+        let actual = onkostar_editor.extract(&["Unbekannt".to_string()]);
+
+        assert!(actual.is_err());
+        assert_eq!(onkostar_editor.editor.data_form.len(), 1);
+    }
+}
@@ -511,6 +511,95 @@ pub struct Entry {
     pub data_form_references: Option<Vec<ReferencedDataForm>>,
 }
 
+impl Entry {
+    /// Checks if the optional element with the given name is present
+    pub fn has_element(&self, name: &str) -> bool {
+        match name {
+            "Beschriftung1" => self.beschriftung1.is_some(),
+            "Beschriftung2" => self.beschriftung2.is_some(),
+            "WertAnzeigenPatmodul" => self.wert_anzeigen_patmodul.is_some(),
+            "DataCatalogueEntryTable" => self.data_catalogue_entry_table.is_some(),
+            "Grafik" => self.grafik.is_some(),
+            "GrafikAusrichtung" => self.grafik_ausrichtung.is_some(),
+            "Datenart" => self.datenart.is_some(),
+            "Filter" => self.filter.is_some(),
+            "Scripts" => self.scripts.is_some(),
+            "ReferencedDataForm" => self.referenced_data_form.is_some(),
+            "ReferencedDataFormField" => self.referenced_data_form_field.is_some(),
+            "AnzeigeAuswahl" => self.anzeige_auswahl.is_some(),
+            "Druckvorlage" => self.druckvorlage.is_some(),
+            "VersionFrom" => self.version_from.is_some(),
+            "Inhalt" => self.inhalt.is_some(),
+            "Hinweis" => self.hinweis.is_some(),
+            "Vorschlagskategorie" => self.vorschlagskategorie.is_some(),
+            "CategoryFilterName" => self.category_filer_name.is_some(),
+            "Platzhalter" => self.platzhalter.is_some(),
+            "InfoAuswahldialog" => self.info_auswahldialog.is_some(),
+            "DiseaseCategoryFilter" => self.disease_category_filter.is_some(),
+            "MindestbreiteLabel" => self.mindestbreite_label.is_some(),
+            "MindestbreiteFeld" => self.mindestbreite_feld.is_some(),
+            "OrganisationunitFilter" => self.ou_filter.is_some(),
+            "Aktion" => self.aktion.is_some(),
+            "Top10" => self.top10.is_some(),
+            "Verschluesselt" => self.verschluesselt.is_some(),
+            "MemoWidth" => self.memo_width.is_some(),
+            "MemoHeight" => self.memo_height.is_some(),
+            "MemoArt" => self.memo_art.is_some(),
+            "Titel" => self.titel.is_some(),
+            "FragebogenItemNummer" => self.fragebogen_item_nummer.is_some(),
+            "Score" => self.score.is_some(),
+            "Kontaktliste" => self.kontaktliste.is_some(),
+            "SucheArt" => self.suche_art.is_some(),
+            "vorherigeWerte" => self.vorherige_werte.is_some(),
+            "EinfuegenVerhindern" => self.einfuegen_verhindern.is_some(),
+            "DataFormReferences" => self.data_form_references.is_some(),
+            _ => false,
+        }
+    }
+
+    /// Type of the form field, e.g. "combobox" or "subform"
+    pub fn entry_type(&self) -> &str {
+        &self.type_
+    }
+
+    /// Mandatory setting of the form field, e.g. "mandatory" or "false"
+    pub fn mandatory(&self) -> &str {
+        &self.mandatory
+    }
+
+    /// Checks if the form field is active
+    pub fn active(&self) -> bool {
+        self.active
+    }
+
+    /// Checks if the form field is read only
+    pub fn read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Name of the section or field group containing this form field, if any
+    pub fn element_parent(&self) -> Option<&str> {
+        Some(self.element_parent.as_str()).filter(|name| !name.is_empty())
+    }
+
+    /// Default value of the form field, if any
+    pub fn default_value(&self) -> Option<&str> {
+        Some(self.default_value.as_str()).filter(|value| !value.is_empty())
+    }
+
+    /// Name of the referenced data catalogue entry, if any
+    pub fn data_catalogue_entry(&self) -> Option<&str> {
+        Some(self.data_catalogue_entry.as_str()).filter(|name| !name.is_empty())
+    }
+
+    /// Name of the data catalogue containing the referenced entry, if given
+    pub fn data_catalogue_entry_table(&self) -> Option<&str> {
+        self.data_catalogue_entry_table
+            .as_deref()
+            .filter(|name| !name.is_empty())
+    }
+}
+
 impl Named for Entry {
     fn get_name(&self) -> String {
         self.name.clone()
@@ -567,29 +656,80 @@ impl UpdatableEntry for Entry {
         self.anzeige_auswahl = Some(value);
     }
 
-    fn update_scripts_code(&mut self, value: String) {
-        self.scripts = Some(Script {
-            code: value,
-            valid: true,
-        });
+    fn update_scripts_code(&mut self, value: String) -> bool {
+        update_value(
+            &mut self.scripts,
+            Some(Script {
+                code: value,
+                valid: true,
+            }),
+        )
     }
 
-    fn update_default_value(&mut self, value: String) {
-        self.default_value = value;
+    fn update_default_value(&mut self, value: String) -> bool {
+        update_value(&mut self.default_value, value)
     }
 
-    fn hide(&mut self) {
-        self.filter = Some(Filter {
-            condition: "false".into(),
-            valid: true,
-            ref_entries: Some(RefEntries { ref_entry: None }),
-        });
-        self.speichern = "0".into();
+    fn hide(&mut self) -> bool {
+        let filter_changed = update_value(
+            &mut self.filter,
+            Some(Filter {
+                condition: "false".into(),
+                valid: true,
+                ref_entries: Some(RefEntries { ref_entry: None }),
+            }),
+        );
+        update_value(&mut self.speichern, "0".into()) || filter_changed
     }
 
-    fn remove_filter(&mut self) {
-        self.filter = None;
+    fn remove_filter(&mut self) -> bool {
+        update_value(&mut self.filter, None)
+    }
+
+    fn update_mandatory(&mut self, value: bool) -> bool {
+        let value = if value { "mandatory" } else { "false" };
+        update_value(&mut self.mandatory, value.to_string())
+    }
+
+    fn update_read_only(&mut self, value: bool) -> bool {
+        update_value(&mut self.read_only, value)
+    }
+
+    fn update_active(&mut self, value: bool) -> bool {
+        update_value(&mut self.active, value)
+    }
+
+    fn update_description(&mut self, value: String) -> bool {
+        update_value(&mut self.description, value)
+    }
+
+    fn update_hinweis(&mut self, value: String) -> bool {
+        // Remove notice if empty
+        update_value(
+            &mut self.hinweis,
+            Some(value).filter(|value| !value.is_empty()),
+        )
+    }
+
+    fn update_position(&mut self, value: String) -> bool {
+        update_value(&mut self.position, value)
+    }
+
+    fn update_element_parent(&mut self, value: String) -> bool {
+        update_value(&mut self.element_parent, value)
+    }
+
+    fn increase_revision(&mut self) {
+        self.revision += 1;
+    }
+}
+
+fn update_value<T: PartialEq>(field: &mut T, value: T) -> bool {
+    if *field == value {
+        return false;
     }
+    *field = value;
+    true
 }
 
 impl Sortable for Entry {
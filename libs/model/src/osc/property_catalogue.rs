@@ -50,6 +50,26 @@ pub struct PropertyCatalogue {
     ordner: Ordner,
 }
 
+impl PropertyCatalogue {
+    /// Codes and short descriptions of the latest version of this property catalogue
+    pub fn codes(&self) -> Vec<(&str, &str)> {
+        self.versions
+            .entry
+            .iter()
+            .flatten()
+            .max_by_key(|version| version.version_number)
+            .and_then(|version| version.entries.as_ref())
+            .map(|entries| {
+                entries
+                    .content
+                    .iter()
+                    .map(|entry| (entry.code.as_str(), entry.short_description.as_str()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
 impl Named for PropertyCatalogue {
     fn get_name(&self) -> String {
         self.name.clone()
@@ -0,0 +1,158 @@
+/*
+ * This file is part of osc-variant
+ *
+ * Copyright (C) 2026 the original author or authors.
+ *
+ * This program is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation; either version 2 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along
+ * with this program; if not, write to the Free Software Foundation, Inc.,
+ * 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
+ */
+
+//! Name patterns with wildcards or regular expressions
+
+use std::fmt::{Display, Formatter};
+
+use regex::Regex;
+use serde::Deserialize;
+
+/// Name or pattern selecting forms or form entries.
+/// Names enclosed in `/` are regular expressions and names containing `*` or `?` are patterns
+/// with wildcards. Patterns and regular expressions must match the whole name.
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Selector {
+    name: String,
+    regex: Option<Regex>,
+}
+
+impl Selector {
+    pub fn is_pattern(&self) -> bool {
+        self.regex.is_some() || self.name.contains(['*', '?'])
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        match &self.regex {
+            Some(regex) => regex.is_match(name),
+            None if self.is_pattern() => matches_pattern(&self.name, name),
+            None => self.name == name,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.name
+    }
+}
+
+impl TryFrom<String> for Selector {
+    type Error = String;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        let regex = match name
+            .strip_prefix('/')
+            .and_then(|expression| expression.strip_suffix('/'))
+        {
+            Some(expression) => Some(
+                Regex::new(&format!("^(?:{expression})$"))
+                    .map_err(|err| format!("Ungültiger regulärer Ausdruck '{name}': {err}"))?,
+            ),
+            None => None,
+        };
+        Ok(Selector { name, regex })
+    }
+}
+
+impl PartialEq for Selector {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl PartialEq<&str> for Selector {
+    fn eq(&self, other: &&str) -> bool {
+        self.name == *other
+    }
+}
+
+impl Display for Selector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Matches a name against a pattern with wildcards `*` for any number of characters
+/// and `?` for exactly one character
+pub fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    n = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use crate::pattern::{Selector, matches_pattern};
+
+    #[test]
+    fn should_match_patterns() {
+        assert!(matches_pattern("Test*", "Testformular"));
+        assert!(matches_pattern("*formular", "Testformular"));
+        assert!(matches_pattern("T?st*lar", "Testformular"));
+        assert!(matches_pattern("Testformular", "Testformular"));
+        assert!(!matches_pattern("Test", "Testformular"));
+        assert!(!matches_pattern("*Test", "Testformular"));
+    }
+
+    #[test]
+    fn should_match_selectors() {
+        let exact = Selector::try_from("DNPM".to_string()).unwrap();
+        assert!(!exact.is_pattern());
+        assert!(exact.matches("DNPM"));
+        assert!(!exact.matches("DNPM Therapieplan"));
+
+        let glob = Selector::try_from("DNPM*".to_string()).unwrap();
+        assert!(glob.is_pattern());
+        assert!(glob.matches("DNPM Therapieplan"));
+
+        let regex = Selector::try_from("/ref_.*_mtb/".to_string()).unwrap();
+        assert!(regex.is_pattern());
+        assert!(regex.matches("ref_first_mtb"));
+        assert!(!regex.matches("ref_first_mtb_alt"));
+
+        assert!(Selector::try_from("/ref_(/".to_string()).is_err());
+    }
+}
@@ -18,16 +18,19 @@
  * 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
  */
 
-use crate::osc::{Named, TypedEntry, UpdatableEntry};
+use crate::osc::{Named, TypedEntry, UpdatableEntry, escape_script};
+use crate::pattern::Selector;
 use serde::Deserialize;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
-fn escape_script(script: &str) -> String {
-    script.replace('\n', "&#10;")
-}
-
 #[derive(Deserialize)]
 pub struct Profile {
+    /// Name of an embedded profile or a profile file this profile is based on
+    #[serde(default)]
+    pub extends: Option<String>,
+    #[serde(default)]
     pub forms: Vec<Form>,
 }
 
@@ -44,6 +47,62 @@ impl Profile {
 
         Profile::from_str(s)
     }
+
+    /// Resolves `extends` recursively and layers this profile on its bases.
+    /// The loader returns the origin, e.g. the file name, and the profile for a name used in `extends`
+    /// and the origin of the profile containing it.
+    pub fn resolve<F>(self, origin: &str, load: &F) -> Result<Profile, String>
+    where
+        F: Fn(&str, &str) -> Result<(String, Profile), String>,
+    {
+        self.resolve_layers(origin, load, &mut vec![origin.to_string()])
+    }
+
+    fn resolve_layers<F>(
+        self,
+        origin: &str,
+        load: &F,
+        chain: &mut Vec<String>,
+    ) -> Result<Profile, String>
+    where
+        F: Fn(&str, &str) -> Result<(String, Profile), String>,
+    {
+        let Some(ref base_name) = self.extends else {
+            return Ok(self);
+        };
+
+        let (base_origin, base) = load(base_name, origin)?;
+        if chain.contains(&base_origin) {
+            chain.push(base_origin);
+            return Err(format!(
+                "Zyklische Vererbung von Profilen: {}",
+                chain
+                    .iter()
+                    .map(|item| format!("'{item}'"))
+                    .collect::<Vec<_>>()
+                    .join(" -> ")
+            ));
+        }
+        chain.push(base_origin.clone());
+
+        let base = base.resolve_layers(&base_origin, load, chain)?;
+        Ok(base.overridden_by(self))
+    }
+
+    /// Layers the given profile on this profile.
+    /// Forms, form references and form fields are merged by name and settings of the layer override
+    /// inherited settings. Entries of the layer with `drop` replace inherited entries instead.
+    pub fn overridden_by(mut self, layer: Profile) -> Profile {
+        for form in layer.forms {
+            match self.forms.iter_mut().find(|item| item.name == form.name) {
+                Some(inherited) if form.drop => *inherited = form,
+                Some(inherited) => inherited.merge(form),
+                None => self.forms.push(form),
+            }
+        }
+        self.extends = None;
+        self
+    }
 }
 
 impl FromStr for Profile {
@@ -60,12 +119,45 @@ impl FromStr for Profile {
 #[allow(clippy::struct_field_names)]
 #[derive(Deserialize)]
 pub struct Form {
-    pub name: String,
+    pub name: Selector,
     #[serde(default)]
     pub form_references: Vec<FormReference>,
     #[serde(default)]
     pub form_fields: Vec<FormField>,
     pub menu_category: Option<MenuCategory>,
+    /// Drop inherited changes of this form
+    #[serde(default)]
+    pub drop: bool,
+}
+
+impl Form {
+    fn merge(&mut self, layer: Form) {
+        for form_reference in layer.form_references {
+            match self
+                .form_references
+                .iter_mut()
+                .find(|item| item.name == form_reference.name)
+            {
+                Some(inherited) if form_reference.drop => *inherited = form_reference,
+                Some(inherited) => inherited.merge(form_reference),
+                None => self.form_references.push(form_reference),
+            }
+        }
+        for form_field in layer.form_fields {
+            match self
+                .form_fields
+                .iter_mut()
+                .find(|item| item.name == form_field.name)
+            {
+                Some(inherited) if form_field.drop => *inherited = form_field,
+                Some(inherited) => inherited.merge(form_field),
+                None => self.form_fields.push(form_field),
+            }
+        }
+        if layer.menu_category.is_some() {
+            self.menu_category = layer.menu_category;
+        }
+    }
 }
 
 pub trait WithScriptsCode {
@@ -94,7 +186,7 @@ where
 
 #[derive(Deserialize)]
 pub struct FormReference {
-    pub name: String,
+    pub name: Selector,
     #[serde(default, deserialize_with = "deserialize_referenced_data_forms")]
     pub referenced_data_form: Option<Vec<String>>,
     pub anzeige: Option<String>,
@@ -102,6 +194,27 @@ pub struct FormReference {
     #[serde(alias = "never_hide", default)]
     pub remove_filter: bool,
     scripts_code: Option<String>,
+    /// Drop inherited changes of this form reference
+    #[serde(default)]
+    pub drop: bool,
+}
+
+impl FormReference {
+    fn merge(&mut self, layer: FormReference) {
+        if layer.referenced_data_form.is_some() {
+            self.referenced_data_form = layer.referenced_data_form;
+        }
+        if layer.anzeige.is_some() {
+            self.anzeige = layer.anzeige;
+        }
+        if layer.anzeige_auswahl.is_some() {
+            self.anzeige_auswahl = layer.anzeige_auswahl;
+        }
+        if layer.scripts_code.is_some() {
+            self.scripts_code = layer.scripts_code;
+        }
+        self.remove_filter |= layer.remove_filter;
+    }
 }
 
 impl WithScriptsCode for FormReference {
@@ -112,13 +225,58 @@ impl WithScriptsCode for FormReference {
 
 #[derive(Deserialize)]
 pub struct FormField {
-    pub name: String,
+    pub name: Selector,
     #[serde(default)]
     pub hide: bool,
     pub default_value: Option<String>,
     #[serde(alias = "never_hide", default)]
     pub remove_filter: bool,
     scripts_code: Option<String>,
+    pub mandatory: Option<bool>,
+    #[serde(alias = "readonly")]
+    pub read_only: Option<bool>,
+    pub active: Option<bool>,
+    pub description: Option<String>,
+    pub hinweis: Option<String>,
+    pub position: Option<String>,
+    pub element_parent: Option<String>,
+    /// Drop inherited changes of this form field
+    #[serde(default)]
+    pub drop: bool,
+}
+
+impl FormField {
+    fn merge(&mut self, layer: FormField) {
+        if layer.default_value.is_some() {
+            self.default_value = layer.default_value;
+        }
+        if layer.scripts_code.is_some() {
+            self.scripts_code = layer.scripts_code;
+        }
+        if layer.mandatory.is_some() {
+            self.mandatory = layer.mandatory;
+        }
+        if layer.read_only.is_some() {
+            self.read_only = layer.read_only;
+        }
+        if layer.active.is_some() {
+            self.active = layer.active;
+        }
+        if layer.description.is_some() {
+            self.description = layer.description;
+        }
+        if layer.hinweis.is_some() {
+            self.hinweis = layer.hinweis;
+        }
+        if layer.position.is_some() {
+            self.position = layer.position;
+        }
+        if layer.element_parent.is_some() {
+            self.element_parent = layer.element_parent;
+        }
+        self.hide |= layer.hide;
+        self.remove_filter |= layer.remove_filter;
+    }
 }
 
 impl WithScriptsCode for FormField {
@@ -134,89 +292,251 @@ pub struct MenuCategory {
     pub column: String,
 }
 
+/// Profile rules applied to at least one form or form entry
+#[derive(Default)]
+pub struct AppliedRules {
+    forms: HashSet<String>,
+    form_references: HashSet<(String, String)>,
+    form_fields: HashSet<(String, String)>,
+    /// Form, form field and parent of form fields not moved into an unknown parent
+    unknown_element_parents: Vec<(String, String, String)>,
+}
+
+impl AppliedRules {
+    /// Returns all rules of the given profile that have not been applied
+    pub fn unmatched(&self, profile: &Profile) -> Vec<UnmatchedRule> {
+        let mut result = vec![];
+        for form in &profile.forms {
+            let form_name = form.name.to_string();
+            if !self.forms.contains(&form_name) {
+                result.push(UnmatchedRule::Form(form_name));
+                continue;
+            }
+            for form_reference in &form.form_references {
+                let key = (form_name.clone(), form_reference.name.to_string());
+                if !self.form_references.contains(&key) {
+                    result.push(UnmatchedRule::FormReference(key.0, key.1));
+                }
+            }
+            for form_field in &form.form_fields {
+                let key = (form_name.clone(), form_field.name.to_string());
+                if !self.form_fields.contains(&key) {
+                    result.push(UnmatchedRule::FormField(key.0, key.1));
+                }
+            }
+        }
+        for (form, form_field, element_parent) in &self.unknown_element_parents {
+            result.push(UnmatchedRule::ElementParent(
+                form.clone(),
+                form_field.clone(),
+                element_parent.clone(),
+            ));
+        }
+        result
+    }
+}
+
+/// Profile rule without a matching form or form entry
+#[derive(Debug, PartialEq)]
+pub enum UnmatchedRule {
+    Form(String),
+    FormReference(String, String),
+    FormField(String, String),
+    /// Section or field group used as `element_parent` not contained in the form
+    ElementParent(String, String, String),
+}
+
+impl Display for UnmatchedRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnmatchedRule::Form(form) => write!(f, "Formular '{form}' nicht gefunden"),
+            UnmatchedRule::FormReference(form, name) => write!(
+                f,
+                "Formularverweis '{name}' in Formular '{form}' nicht gefunden"
+            ),
+            UnmatchedRule::FormField(form, name) => {
+                write!(
+                    f,
+                    "Formularfeld '{name}' in Formular '{form}' nicht gefunden"
+                )
+            }
+            UnmatchedRule::ElementParent(form, name, element_parent) => write!(
+                f,
+                "Übergeordnetes Element '{element_parent}' für Formularfeld '{name}' in Formular '{form}' nicht gefunden"
+            ),
+        }
+    }
+}
+
 pub trait ProfileApplicable
 where
     Self: Named,
 {
-    fn apply_profile(&mut self, profile: &Profile);
+    fn apply_profile(&mut self, profile: &Profile, applied: &mut AppliedRules);
 }
 
 impl<Type: 'static> ProfileApplicable for crate::osc::form::Form<Type> {
-    fn apply_profile(&mut self, profile: &Profile) {
-        profile.forms.iter().for_each(|profile_form| {
-            if self.get_name() == profile_form.name
-                && let Some(ref mut entries) = self.entries
-            {
+    fn apply_profile(&mut self, profile: &Profile, applied: &mut AppliedRules) {
+        let mut has_updates = false;
+        let entry_names = self
+            .entries()
+            .iter()
+            .map(Named::get_name)
+            .collect::<Vec<_>>();
+        for profile_form in select(&profile.forms, &self.get_name(), |form| &form.name) {
+            applied.forms.insert(profile_form.name.to_string());
+
+            if let Some(ref mut entries) = self.entries {
                 entries.entry.iter_mut().for_each(|entry| {
-                    profile_form
-                        .form_references
-                        .iter()
-                        .for_each(|form_reference| {
+                    let name = entry.get_name();
+
+                    if entry.is_form_reference() {
+                        for form_reference in
+                            select(&profile_form.form_references, &name, |item| &item.name)
+                        {
                             apply_profile_to_form_entry(entry, form_reference);
-                        });
+                            applied.form_references.insert((
+                                profile_form.name.to_string(),
+                                form_reference.name.to_string(),
+                            ));
+                        }
+                    }
 
                     // Hide form field using filter set to "false" if requested and change default value
-                    profile_form
-                        .form_fields
-                        .iter()
-                        .for_each(|form_field| apply_profile_to_form_field(entry, form_field));
-
-                    if let Some(menu_category) = &profile_form.menu_category {
-                        self.menu_category = Some(crate::osc::MenuCategory {
-                            name: menu_category.name.clone(),
-                            position: menu_category.position.clone(),
-                            column: menu_category.column.clone(),
-                        });
+                    for form_field in select(&profile_form.form_fields, &name, |item| &item.name) {
+                        // Only move the form field into another entry of the same form
+                        let unknown_element_parent =
+                            form_field.element_parent.as_ref().filter(|parent| {
+                                !parent.is_empty()
+                                    && (**parent == name || !entry_names.contains(parent))
+                            });
+                        if let Some(element_parent) = unknown_element_parent {
+                            let key = (
+                                profile_form.name.to_string(),
+                                name.clone(),
+                                element_parent.clone(),
+                            );
+                            if !applied.unknown_element_parents.contains(&key) {
+                                applied.unknown_element_parents.push(key);
+                            }
+                        }
+                        has_updates |= apply_profile_to_form_field(
+                            entry,
+                            form_field,
+                            unknown_element_parent.is_none(),
+                        );
+                        applied
+                            .form_fields
+                            .insert((profile_form.name.to_string(), form_field.name.to_string()));
                     }
                 });
+
+                if let Some(menu_category) = &profile_form.menu_category {
+                    self.menu_category = Some(crate::osc::MenuCategory {
+                        name: menu_category.name.clone(),
+                        position: menu_category.position.clone(),
+                        column: menu_category.column.clone(),
+                    });
+                }
             }
-        });
+        }
+
+        if has_updates {
+            self.revision += 1;
+        }
     }
 }
 
+/// Selects all rules for the given name in the order they are applied.
+/// Rules with a matching pattern are applied first in order of the profile, rules with the exact name
+/// are applied afterwards and override settings of rules with a pattern.
+fn select<'a, T>(rules: &'a [T], name: &str, selector: impl Fn(&T) -> &Selector) -> Vec<&'a T> {
+    let matching = rules
+        .iter()
+        .filter(|rule| selector(rule).matches(name))
+        .collect::<Vec<_>>();
+    matching
+        .iter()
+        .filter(|rule| selector(rule).is_pattern())
+        .chain(matching.iter().filter(|rule| !selector(rule).is_pattern()))
+        .copied()
+        .collect()
+}
+
 fn apply_profile_to_form_entry<E>(entry: &mut E, form_reference: &FormReference)
 where
-    E: UpdatableEntry + TypedEntry,
+    E: UpdatableEntry,
 {
-    if entry.is_form_reference() && entry.get_name() == form_reference.name {
-        if let Some(profile_referenced_data_forms) = &form_reference.referenced_data_form {
-            for profile_referenced_data_form in profile_referenced_data_forms {
-                entry.update_referenced_data_form(profile_referenced_data_form.clone());
-            }
-        }
-        if let Some(profile_anzeige) = &form_reference.anzeige {
-            entry.update_anzeige(profile_anzeige.clone());
-        }
-        if let Some(profile_anzeige_auswahl) = &form_reference.anzeige_auswahl {
-            entry.update_anzeige_auswahl(profile_anzeige_auswahl.clone());
-        }
-        if let Some(scripts_code) = &form_reference.escaped_scripts_code() {
-            entry.update_scripts_code(scripts_code.clone());
-        }
-        if form_reference.remove_filter {
-            entry.remove_filter();
+    if let Some(profile_referenced_data_forms) = &form_reference.referenced_data_form {
+        for profile_referenced_data_form in profile_referenced_data_forms {
+            entry.update_referenced_data_form(profile_referenced_data_form.clone());
         }
     }
+    if let Some(profile_anzeige) = &form_reference.anzeige {
+        entry.update_anzeige(profile_anzeige.clone());
+    }
+    if let Some(profile_anzeige_auswahl) = &form_reference.anzeige_auswahl {
+        entry.update_anzeige_auswahl(profile_anzeige_auswahl.clone());
+    }
+    if let Some(scripts_code) = &form_reference.escaped_scripts_code() {
+        entry.update_scripts_code(scripts_code.clone());
+    }
+    if form_reference.remove_filter {
+        entry.remove_filter();
+    }
 }
 
-fn apply_profile_to_form_field<E>(entry: &mut E, form_field: &FormField)
+/// Applies the form field rule and returns whether entry attributes have been changed.
+/// The revision of the entry is increased on changes of entry attributes.
+/// The element parent is only applied if it is known.
+fn apply_profile_to_form_field<E>(
+    entry: &mut E,
+    form_field: &FormField,
+    known_element_parent: bool,
+) -> bool
 where
     E: UpdatableEntry,
 {
-    if entry.get_name() == form_field.name {
-        if form_field.hide {
-            entry.hide();
-        }
-        if let Some(new_default_value) = &form_field.default_value {
-            entry.update_default_value(new_default_value.clone());
-        }
-        if let Some(scripts_code) = &form_field.escaped_scripts_code() {
-            entry.update_scripts_code(scripts_code.clone());
-        }
-        if form_field.remove_filter {
-            entry.remove_filter();
-        }
+    let mut changed = false;
+    if form_field.hide {
+        changed |= entry.hide();
+    }
+    if let Some(new_default_value) = &form_field.default_value {
+        changed |= entry.update_default_value(new_default_value.clone());
+    }
+    if let Some(scripts_code) = &form_field.escaped_scripts_code() {
+        changed |= entry.update_scripts_code(scripts_code.clone());
+    }
+    if form_field.remove_filter {
+        changed |= entry.remove_filter();
+    }
+    if let Some(mandatory) = form_field.mandatory {
+        changed |= entry.update_mandatory(mandatory);
     }
+    if let Some(read_only) = form_field.read_only {
+        changed |= entry.update_read_only(read_only);
+    }
+    if let Some(active) = form_field.active {
+        changed |= entry.update_active(active);
+    }
+    if let Some(description) = &form_field.description {
+        changed |= entry.update_description(description.clone());
+    }
+    if let Some(hinweis) = &form_field.hinweis {
+        changed |= entry.update_hinweis(hinweis.clone());
+    }
+    if let Some(position) = &form_field.position {
+        changed |= entry.update_position(position.clone());
+    }
+    if let Some(element_parent) = &form_field.element_parent
+        && known_element_parent
+    {
+        changed |= entry.update_element_parent(element_parent.clone());
+    }
+    if changed {
+        entry.increase_revision();
+    }
+    changed
 }
 
 #[allow(clippy::panic)]
@@ -255,6 +575,84 @@ mod tests {
         }
     }
 
+    #[test]
+    fn should_layer_profiles_by_name() {
+        let base = "forms:
+               - name: 'DNPM Therapieplan'
+                 form_references:
+                   - name: ref_first_mtb
+                     referenced_data_form: 'OS.Tumorkonferenz'
+                     anzeige: 'Datum: {Datum}'
+                 form_fields:
+                   - name: eingabefeld
+                     hide: true
+                   - name: anderesfeld
+                     default_value: 'A'
+            ";
+        let layer = "
+            extends: base.yml
+            forms:
+              - name: 'DNPM Therapieplan'
+                form_references:
+                  - name: ref_first_mtb
+                    referenced_data_form: 'OS.Tumorkonferenz.VarianteUKW'
+                form_fields:
+                  - name: eingabefeld
+                    drop: true
+              - name: 'DNPM Klinik/Anamnese'
+            ";
+
+        let (Ok(base), Ok(layer)) = (Profile::from_str(base), Profile::from_str(layer)) else {
+            panic!("Cannot deserialize profiles");
+        };
+        assert_eq!(layer.extends, Some("base.yml".to_string()));
+
+        let profile = base.overridden_by(layer);
+        assert_eq!(profile.forms.len(), 2);
+
+        let form_reference = &profile.forms[0].form_references[0];
+        assert_eq!(
+            form_reference.referenced_data_form,
+            Some(vec!["OS.Tumorkonferenz.VarianteUKW".to_string()])
+        );
+        assert_eq!(form_reference.anzeige, Some("Datum: {Datum}".to_string()));
+
+        let form_fields = &profile.forms[0].form_fields;
+        assert_eq!(form_fields.len(), 2);
+        assert!(!form_fields[0].hide);
+        assert_eq!(form_fields[1].default_value, Some("A".to_string()));
+    }
+
+    #[test]
+    fn should_resolve_extends() {
+        let load = |name: &str, _: &str| match name {
+            "base.yml" => Ok((name.to_string(), Profile::from_str("extends: UKW")?)),
+            "UKW" => Ok((name.to_string(), Profile::embedded_profile(name)?)),
+            "cycle.yml" => Ok((name.to_string(), Profile::from_str("extends: cycle.yml")?)),
+            _ => Err(format!("Unbekanntes Profil '{name}'")),
+        };
+
+        match Profile::from_str("extends: base.yml")
+            .and_then(|profile| profile.resolve("site.yml", &load))
+        {
+            Ok(profile) => {
+                assert!(profile.extends.is_none());
+                assert_eq!(profile.forms[0].name, "DNPM Klinik/Anamnese");
+            }
+            Err(e) => panic!("Cannot resolve profile: {e}"),
+        }
+
+        match Profile::from_str("extends: cycle.yml")
+            .and_then(|profile| profile.resolve("site.yml", &load))
+        {
+            Ok(_) => panic!("Cycle not detected"),
+            Err(e) => assert_eq!(
+                e,
+                "Zyklische Vererbung von Profilen: 'site.yml' -> 'cycle.yml' -> 'cycle.yml'"
+            ),
+        }
+    }
+
     #[test]
     fn should_deserialize_form_reference() {
         let content = "forms:
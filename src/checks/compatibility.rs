@@ -0,0 +1,391 @@
+/*
+ * This file is part of osc-variant
+ *
+ * Copyright (C) 2026 the original author or authors.
+ *
+ * This program is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation; either version 2 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along
+ * with this program; if not, write to the Free Software Foundation, Inc.,
+ * 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
+ */
+
+//! Compatibility of the used features with a specific Onkostar version
+
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use model::osc::form::{DataFormType, Form, UnterformularType};
+use model::osc::onkostar_editor::OnkostarEditor;
+use model::osc::other::Entry;
+use model::osc::{Named, TypedEntry};
+use serde::Deserialize;
+
+use crate::checks::CheckNotice;
+use crate::checks::CheckNotice::{ErrorWithCode, InfoWithCode, WarningWithCode};
+
+/// Onkostar version with any number of numeric parts, e.g. `2.13.3`
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct OnkostarVersion(Vec<u32>);
+
+impl FromStr for OnkostarVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim()
+            .split('.')
+            .map(str::parse::<u32>)
+            .collect::<Result<Vec<_>, _>>()
+            .map(OnkostarVersion)
+            .map_err(|_| format!("Ungültige Onkostar-Version '{s}'"))
+    }
+}
+
+impl TryFrom<String> for OnkostarVersion {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        OnkostarVersion::from_str(&value)
+    }
+}
+
+impl Ord for OnkostarVersion {
+    /// Compares versions part by part, missing parts are treated as `0`
+    fn cmp(&self, other: &Self) -> Ordering {
+        (0..self.0.len().max(other.0.len()))
+            .map(|index| {
+                let part = |version: &Self| version.0.get(index).copied().unwrap_or_default();
+                part(self).cmp(&part(other))
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for OnkostarVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for OnkostarVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.0
+                .iter()
+                .map(u32::to_string)
+                .collect::<Vec<_>>()
+                .join(".")
+        )
+    }
+}
+
+/// Usage of a feature by forms, subforms or their entries
+pub enum Usage {
+    Form {
+        data_form: fn(&Form<DataFormType>) -> bool,
+        unterformular: fn(&Form<UnterformularType>) -> bool,
+    },
+    Entry(fn(&Entry) -> bool),
+    /// Optional element of form fields, e.g. `DataFormReferences`
+    EntryAttribute(&'static str),
+}
+
+/// Feature only available since a specific Onkostar version
+pub struct Feature {
+    pub name: &'static str,
+    pub since: &'static str,
+    /// Code of the info notice used instead if no target version is given.
+    /// Features with a notice code are only reported without a target version.
+    pub notice: Option<&'static str>,
+    pub usage: Usage,
+}
+
+impl Feature {
+    /// Forms, subforms and form fields using this feature
+    fn used_by(&self, data: &OnkostarEditor) -> Vec<String> {
+        match self.usage {
+            Usage::Form {
+                data_form,
+                unterformular,
+            } => data
+                .editor
+                .data_form
+                .iter()
+                .filter(|form| data_form(form))
+                .map(|form| format!("'{}'", form.get_name()))
+                .chain(
+                    data.editor
+                        .unterformular
+                        .iter()
+                        .filter(|form| unterformular(form))
+                        .map(|form| format!("'{}'", form.get_name())),
+                )
+                .collect(),
+            Usage::Entry(_) | Usage::EntryAttribute(_) => {
+                let uses = |item: &Entry| match self.usage {
+                    Usage::Entry(entry) => entry(item),
+                    Usage::EntryAttribute(name) => item.has_element(name),
+                    Usage::Form { .. } => false,
+                };
+                let entries_of = |form_name: String, entries: &[Entry]| {
+                    entries
+                        .iter()
+                        .filter(|item| uses(item))
+                        .map(|item| format!("Formularfeld '{}' in '{form_name}'", item.get_name()))
+                        .collect::<Vec<_>>()
+                };
+                data.editor
+                    .data_form
+                    .iter()
+                    .flat_map(|form| entries_of(form.get_name(), form.entries()))
+                    .chain(
+                        data.editor
+                            .unterformular
+                            .iter()
+                            .flat_map(|form| entries_of(form.get_name(), form.entries())),
+                    )
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Features of forms, subforms and form fields only available since a specific Onkostar version.
+/// New features have to be added here, the source of the version is given for each feature.
+pub static FEATURES: [Feature; 3] = [
+    // Onkostar 2.14.0 introduced references to multiple forms, see problem 2026-0006
+    Feature {
+        name: "Formularverweise nur mit Angabe mehrerer möglicher Formulare",
+        since: "2.14.0",
+        notice: Some("2026-0006"),
+        usage: Usage::Entry(uses_data_form_references_only),
+    },
+    // Onkostar 2.14.0 introduced references to multiple forms, see problem 2026-0006
+    Feature {
+        name: "Angabe mehrerer möglicher Formulare ('DataFormReferences')",
+        since: "2.14.0",
+        notice: None,
+        usage: Usage::EntryAttribute("DataFormReferences"),
+    },
+    // Not contained in exports of Onkostar 2.13 and older
+    Feature {
+        name: "Anzeige des Verlaufs ('ShowHistoryButton')",
+        since: "2.14.0",
+        notice: None,
+        usage: Usage::Form {
+            data_form: Form::has_show_history_button,
+            unterformular: Form::has_show_history_button,
+        },
+    },
+];
+
+/// Form reference without the legacy reference to a single form
+fn uses_data_form_references_only(entry: &Entry) -> bool {
+    entry.is_form_reference()
+        && entry.referenced_data_form.is_none()
+        && entry.data_form_references.is_some()
+}
+
+/// Checks for features not available in the target version and for files
+/// created by a newer Onkostar version than the target version.
+/// Without a target version, only features with a notice code are reported as info.
+pub fn check(data: &OnkostarEditor, target_version: Option<&OnkostarVersion>) -> Vec<CheckNotice> {
+    let mut result = vec![];
+
+    if let Some(target_version) = target_version
+        && let Ok(version) = OnkostarVersion::from_str(&data.info_xml.version)
+        && &version > target_version
+    {
+        result.push(WarningWithCode {
+            code: "2026-0016".to_string(),
+            description: format!(
+                "Datei wurde mit Onkostar {version} erstellt, die Zielversion ist {target_version}"
+            ),
+            line: None,
+            example: None,
+        });
+    }
+
+    for feature in &FEATURES {
+        // Versions of all features are checked by tests
+        let Ok(since) = OnkostarVersion::from_str(feature.since) else {
+            continue;
+        };
+        match target_version {
+            Some(target_version) if feature.notice.is_none() && &since > target_version => {
+                for location in feature.used_by(data) {
+                    result.push(ErrorWithCode {
+                        code: "2026-0015".to_string(),
+                        description: format!(
+                            "{location} verwendet '{}', erst ab Onkostar {since} verfügbar - Zielversion ist {target_version}",
+                            feature.name
+                        ),
+                        line: None,
+                        example: None,
+                    });
+                }
+            }
+            None => {
+                let Some(code) = feature.notice else {
+                    continue;
+                };
+                for location in feature.used_by(data) {
+                    result.push(InfoWithCode {
+                        code: code.to_string(),
+                        description: format!(
+                            "{location} verwendet '{}', erst in neueren Onkostar-Versionen ab {since} verfügbar",
+                            feature.name
+                        ),
+                        line: None,
+                        example: None,
+                    });
+                }
+            }
+            Some(_) => {}
+        }
+    }
+
+    result
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use model::osc::onkostar_editor::OnkostarEditor;
+
+    use crate::checks::CheckNotice;
+    use crate::checks::compatibility::{FEATURES, OnkostarVersion, check};
+
+    #[test]
+    fn should_compare_versions() {
+        let version = |s: &str| OnkostarVersion::from_str(s).unwrap();
+
+        assert!(version("2.13.3") < version("2.14.0"));
+        assert!(version("2.14") == version("2.14").max(version("2.13.99")));
+        assert_eq!(
+            version("2.14").cmp(&version("2.14.0")),
+            std::cmp::Ordering::Equal
+        );
+        assert!(version("2.14.0.1") > version("2.14.0"));
+        assert!(OnkostarVersion::from_str("2.x").is_err());
+    }
+
+    #[test]
+    fn should_parse_versions_of_all_features() {
+        for feature in &FEATURES {
+            assert!(
+                OnkostarVersion::from_str(feature.since).is_ok(),
+                "{}",
+                feature.name
+            );
+        }
+    }
+
+    fn notices(data: &OnkostarEditor, target_version: Option<&str>) -> Vec<String> {
+        check(
+            data,
+            target_version
+                .map(|version| OnkostarVersion::from_str(version).unwrap())
+                .as_ref(),
+        )
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+    }
+
+    #[test]
+    fn should_report_features_not_available_in_target_version() {
+        let data =
+            OnkostarEditor::from_str(&include_str!("../../libs/model/tests/test.osc").replacen(
+                "<TudokReadonly>",
+                "<ShowHistoryButton>true</ShowHistoryButton><TudokReadonly>",
+                1,
+            ))
+            .unwrap();
+
+        let notices = notices(&data, Some("2.13.3"));
+
+        assert_eq!(notices.len(), 2);
+        assert!(notices[0].contains("Formularfeld 'Formularverweis' in 'Hauptformular'"));
+        assert!(notices[0].contains("DataFormReferences"));
+        assert!(notices[1].contains("ShowHistoryButton"));
+        assert!(check(&data, Some(&OnkostarVersion::from_str("2.14.0").unwrap())).is_empty());
+    }
+
+    #[test]
+    fn should_not_report_disabled_history_button() {
+        let data =
+            OnkostarEditor::from_str(&include_str!("../../libs/model/tests/test.osc").replacen(
+                "<TudokReadonly>",
+                "<ShowHistoryButton>false</ShowHistoryButton><TudokReadonly>",
+                1,
+            ))
+            .unwrap();
+
+        assert!(
+            !notices(&data, Some("2.13.3"))
+                .iter()
+                .any(|notice| notice.contains("ShowHistoryButton"))
+        );
+    }
+
+    #[test]
+    fn should_report_form_references_without_legacy_reference_as_info() {
+        let data =
+            OnkostarEditor::from_str(&include_str!("../../libs/model/tests/test.osc").replacen(
+                "<ReferencedDataForm>Anderes Formular</ReferencedDataForm>\n",
+                "",
+                1,
+            ))
+            .unwrap();
+
+        let codes = |target_version: Option<&OnkostarVersion>| {
+            check(&data, target_version)
+                .iter()
+                .filter_map(|notice| notice.code().map(ToString::to_string))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(codes(None), vec!["2026-0006"]);
+        assert_eq!(
+            codes(Some(&OnkostarVersion::from_str("2.13.3").unwrap())),
+            vec!["2026-0015"]
+        );
+        assert!(codes(Some(&OnkostarVersion::from_str("2.14.0").unwrap())).is_empty());
+        assert!(
+            check(
+                &OnkostarEditor::from_str(include_str!("../../libs/model/tests/test.osc")).unwrap(),
+                None
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn should_report_files_of_newer_versions() {
+        let data =
+            OnkostarEditor::from_str(include_str!("../../libs/model/tests/test.osc")).unwrap();
+
+        let notices = check(&data, Some(&OnkostarVersion::from_str("2.11.0").unwrap()));
+
+        assert!(matches!(
+            &notices[0],
+            CheckNotice::WarningWithCode { code, .. } if code == "2026-0016"
+        ));
+    }
+}
@@ -0,0 +1,157 @@
+/*
+ * This file is part of osc-variant
+ *
+ * Copyright (C) 2026 the original author or authors.
+ *
+ * This program is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation; either version 2 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along
+ * with this program; if not, write to the Free Software Foundation, Inc.,
+ * 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
+ */
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use model::osc::Named;
+use model::osc::onkostar_editor::OnkostarEditor;
+use model::pattern::matches_pattern;
+use serde::Deserialize;
+
+use crate::checks::compatibility::OnkostarVersion;
+use crate::checks::{CheckNotice, Problem, Severity};
+
+/// Default name of the check configuration file in the current directory
+pub const DEFAULT_CONFIG_FILE: &str = ".osc-variant-checks.yml";
+
+/// Project specific configuration of checks
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CheckConfig {
+    /// Codes of checks not to be applied
+    #[serde(default)]
+    pub disabled: Vec<String>,
+    /// Severity of notices by code, overriding the default severity
+    #[serde(default)]
+    pub severity: BTreeMap<String, Severity>,
+    /// Name patterns of forms not to be checked, `*` and `?` can be used as wildcards
+    #[serde(default)]
+    pub ignore_forms: Vec<String>,
+    /// Onkostar version the file is checked for compatibility with
+    #[serde(default)]
+    pub target_version: Option<OnkostarVersion>,
+}
+
+impl FromStr for CheckConfig {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_yaml::from_str::<CheckConfig>(s).map_err(|err| err.to_string())
+    }
+}
+
+impl CheckConfig {
+    pub fn is_disabled(&self, code: &str) -> bool {
+        self.disabled.iter().any(|disabled| disabled == code)
+    }
+
+    /// Effective severity of the given problem
+    pub fn severity_of(&self, problem: &Problem) -> Severity {
+        self.severity
+            .get(problem.code)
+            .copied()
+            .unwrap_or(problem.severity)
+    }
+
+    pub fn is_ignored_form(&self, name: &str) -> bool {
+        self.ignore_forms
+            .iter()
+            .any(|pattern| matches_pattern(pattern, name))
+    }
+
+    /// Removes all forms to be ignored
+    pub fn remove_ignored_forms(&self, data: &mut OnkostarEditor) {
+        data.editor
+            .data_form
+            .retain(|form| !self.is_ignored_form(&form.get_name()));
+        data.editor
+            .unterformular
+            .retain(|form| !self.is_ignored_form(&form.get_name()));
+    }
+
+    /// Removes notices of disabled checks and applies configured severities
+    pub fn apply(&self, notices: Vec<CheckNotice>) -> Vec<CheckNotice> {
+        notices
+            .into_iter()
+            .filter(|notice| notice.code().is_none_or(|code| !self.is_disabled(code)))
+            .map(
+                |notice| match notice.code().and_then(|code| self.severity.get(code)) {
+                    Some(&severity) => notice.with_severity(severity),
+                    None => notice,
+                },
+            )
+            .collect()
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::checks::config::CheckConfig;
+    use crate::checks::{CheckNotice, Severity};
+
+    #[test]
+    fn should_apply_config() {
+        let config = CheckConfig::from_str(
+            "
+             disabled:
+               - '2026-0006'
+             severity:
+               '2023-0004': warning
+             ignore_forms:
+               - 'Test*'
+             target_version: '2.13.3'
+            ",
+        )
+        .unwrap();
+
+        let notice = |code: &str| CheckNotice::ErrorWithCode {
+            code: code.to_string(),
+            description: "Test".to_string(),
+            line: None,
+            example: None,
+        };
+
+        let notices = config.apply(vec![
+            notice("2023-0004"),
+            notice("2026-0006"),
+            notice("2024-0005"),
+        ]);
+
+        assert_eq!(notices.len(), 2);
+        assert!(matches!(
+            notices[0],
+            CheckNotice::WarningWithCode { ref code, .. } if code == "2023-0004"
+        ));
+        assert!(matches!(
+            notices[1],
+            CheckNotice::ErrorWithCode { ref code, .. } if code == "2024-0005"
+        ));
+        assert_eq!(config.severity["2023-0004"], Severity::Warning);
+        assert!(config.is_ignored_form("Testformular"));
+        assert_eq!(
+            config.target_version.map(|version| version.to_string()),
+            Some("2.13.3".to_string())
+        );
+    }
+}
@@ -17,19 +17,29 @@
  * with this program; if not, write to the Free Software Foundation, Inc.,
  * 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
  */
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::{Display, Formatter};
 use std::path::Path;
+use std::sync::LazyLock;
 
-use crate::checks::CheckNotice::{ErrorWithCode, Info};
-use console::style;
+use crate::checks::CheckNotice::{ErrorWithCode, InfoWithCode, WarningWithCode};
+use crate::checks::config::CheckConfig;
+use crate::library::{Library, external_requirement};
+use console::{StyledObject, style};
 use model::osc::form::{DataFormType, Form, UnterformularType};
 use model::osc::onkostar_editor::OnkostarEditor;
+use model::osc::other::Entry;
 use model::osc::requirements::{Requirement, Requires};
-use model::osc::{Comparable, Named, TypedEntry};
+use model::osc::{Comparable, Filter, Named, TypedEntry};
+use regex::Regex;
+use serde::Deserialize;
 
+pub mod compatibility;
+pub mod config;
 #[cfg(feature = "unzip-osb")]
 pub mod osb;
 pub mod osc;
+pub mod sarif;
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -46,11 +56,25 @@ pub enum CheckNotice {
         description: String,
         line: Option<usize>,
     },
+    /// Other known issues with a support code
+    WarningWithCode {
+        code: String,
+        description: String,
+        line: Option<usize>,
+        example: Option<String>,
+    },
     /// Other known issues
     Warning {
         description: String,
         line: Option<usize>,
     },
+    /// Other known issues with a support code
+    InfoWithCode {
+        code: String,
+        description: String,
+        line: Option<usize>,
+        example: Option<String>,
+    },
     /// Other known issues
     Info {
         description: String,
@@ -58,52 +82,210 @@ pub enum CheckNotice {
     },
     /// Ok
     Ok(String),
+    /// Progress of checking the entries of a file, not a result of a check itself
+    Progress {
+        description: String,
+        severity: Severity,
+    },
+}
+
+/// Severity of notices
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Severity::Error => "Fehler",
+                Severity::Warning => "Warnung",
+                Severity::Info => "Information",
+            }
+        )
+    }
 }
 
 impl CheckNotice {
     fn variant_order(&self) -> u8 {
         match self {
             CheckNotice::ErrorWithCode { .. } | CheckNotice::Error { .. } => 1,
-            CheckNotice::Warning { .. } => 2,
-            CheckNotice::Info { .. } => 3,
+            CheckNotice::WarningWithCode { .. } | CheckNotice::Warning { .. } => 2,
+            CheckNotice::InfoWithCode { .. } | CheckNotice::Info { .. } => 3,
             CheckNotice::Ok(_) => 4,
+            CheckNotice::Progress { .. } => 5,
         }
     }
-}
 
-impl Display for CheckNotice {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    /// Returns `true` if this notice has the given or a higher severity.
+    /// Notices about the progress of a check never reach any severity.
+    pub fn is_at_least(&self, severity: Severity) -> bool {
+        let order = match severity {
+            Severity::Error => 1,
+            Severity::Warning => 2,
+            Severity::Info => 3,
+        };
+        self.variant_order() <= order
+    }
+
+    /// Returns the support code, if any
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            CheckNotice::ErrorWithCode { code, .. }
+            | CheckNotice::WarningWithCode { code, .. }
+            | CheckNotice::InfoWithCode { code, .. } => Some(code),
+            _ => None,
+        }
+    }
+
+    /// Changes the severity of a notice with support code. Other notices are unchanged.
+    pub fn with_severity(self, severity: Severity) -> Self {
         match self {
             CheckNotice::ErrorWithCode {
                 code,
                 description,
                 line,
                 example,
-            } => match line {
-                Some(line) => write!(
-                    f,
-                    "{: <7} ({}) at Line {}: {}{}",
-                    style("ERROR").red().bold(),
+            }
+            | CheckNotice::WarningWithCode {
+                code,
+                description,
+                line,
+                example,
+            }
+            | CheckNotice::InfoWithCode {
+                code,
+                description,
+                line,
+                example,
+            } => match severity {
+                Severity::Error => CheckNotice::ErrorWithCode {
                     code,
+                    description,
                     line,
+                    example,
+                },
+                Severity::Warning => CheckNotice::WarningWithCode {
+                    code,
                     description,
-                    match example {
-                        Some(example) => format!("\n        🔥 '{}'", style(example).dim()),
-                        _ => String::new(),
-                    }
-                ),
-                None => write!(
-                    f,
-                    "{: <7} ({}): {}{}",
-                    style("ERROR").red().bold(),
+                    line,
+                    example,
+                },
+                Severity::Info => CheckNotice::InfoWithCode {
                     code,
                     description,
-                    match example {
-                        Some(example) => format!("\n        🔥 '{}'", style(example).dim()),
-                        _ => String::new(),
-                    }
-                ),
+                    line,
+                    example,
+                },
             },
+            notice => notice,
+        }
+    }
+
+    /// Prefixes the description with the name of the checked file or archive entry
+    #[cfg(feature = "unzip-osb")]
+    fn in_entry(self, name: &str) -> Self {
+        match self {
+            CheckNotice::ErrorWithCode {
+                code,
+                description,
+                line,
+                example,
+            } => CheckNotice::ErrorWithCode {
+                code,
+                description: format!("'{name}': {description}"),
+                line,
+                example,
+            },
+            CheckNotice::Error { description, line } => CheckNotice::Error {
+                description: format!("'{name}': {description}"),
+                line,
+            },
+            CheckNotice::WarningWithCode {
+                code,
+                description,
+                line,
+                example,
+            } => CheckNotice::WarningWithCode {
+                code,
+                description: format!("'{name}': {description}"),
+                line,
+                example,
+            },
+            CheckNotice::Warning { description, line } => CheckNotice::Warning {
+                description: format!("'{name}': {description}"),
+                line,
+            },
+            CheckNotice::InfoWithCode {
+                code,
+                description,
+                line,
+                example,
+            } => CheckNotice::InfoWithCode {
+                code,
+                description: format!("'{name}': {description}"),
+                line,
+                example,
+            },
+            CheckNotice::Info { description, line } => CheckNotice::Info {
+                description: format!("'{name}': {description}"),
+                line,
+            },
+            CheckNotice::Ok(description) => CheckNotice::Ok(format!("'{name}': {description}")),
+            CheckNotice::Progress {
+                description,
+                severity,
+            } => CheckNotice::Progress {
+                description: format!("'{name}': {description}"),
+                severity,
+            },
+        }
+    }
+}
+
+fn write_with_code(
+    f: &mut Formatter<'_>,
+    label: &StyledObject<&str>,
+    code: &str,
+    description: &str,
+    line: Option<usize>,
+    example: Option<&String>,
+) -> std::fmt::Result {
+    let example = match example {
+        Some(example) => format!("\n        🔥 '{}'", style(example).dim()),
+        _ => String::new(),
+    };
+    match line {
+        Some(line) => write!(
+            f,
+            "{label: <7} ({code}) at Line {line}: {description}{example}"
+        ),
+        None => write!(f, "{label: <7} ({code}): {description}{example}"),
+    }
+}
+
+impl Display for CheckNotice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckNotice::ErrorWithCode {
+                code,
+                description,
+                line,
+                example,
+            } => write_with_code(
+                f,
+                &style("ERROR").red().bold(),
+                code,
+                description,
+                *line,
+                example.as_ref(),
+            ),
             CheckNotice::Error { description, line } => match line {
                 Some(line) => write!(
                     f,
@@ -114,6 +296,19 @@ impl Display for CheckNotice {
                 ),
                 None => write!(f, "{: <7} {}", style("ERROR").red().bold(), description),
             },
+            CheckNotice::WarningWithCode {
+                code,
+                description,
+                line,
+                example,
+            } => write_with_code(
+                f,
+                &style("WARNING").yellow().bold(),
+                code,
+                description,
+                *line,
+                example.as_ref(),
+            ),
             CheckNotice::Warning { description, line } => match line {
                 Some(line) => write!(
                     f,
@@ -129,6 +324,19 @@ impl Display for CheckNotice {
                     description
                 ),
             },
+            CheckNotice::InfoWithCode {
+                code,
+                description,
+                line,
+                example,
+            } => write_with_code(
+                f,
+                &style("INFO").blue().bold(),
+                code,
+                description,
+                *line,
+                example.as_ref(),
+            ),
             CheckNotice::Info { description, line } => match line {
                 Some(line) => write!(
                     f,
@@ -140,6 +348,17 @@ impl Display for CheckNotice {
                 None => write!(f, "{: <7} {}", style("INFO").blue().bold(), description),
             },
             CheckNotice::Ok(msg) => write!(f, "{: <7} {}", style("OK").green(), msg),
+            CheckNotice::Progress {
+                description,
+                severity,
+            } => {
+                let label = match severity {
+                    Severity::Error => style("ERROR").red().bold(),
+                    Severity::Warning => style("WARNING").yellow().bold(),
+                    Severity::Info => style("INFO").blue().bold(),
+                };
+                write!(f, "{label: <7} {description}")
+            }
         }
     }
 }
@@ -148,15 +367,20 @@ pub trait Checkable {
     fn check(&self) -> Vec<CheckNotice>;
 }
 
-#[allow(unused)]
 pub trait Fixable {
-    fn fix(&mut self) -> bool;
+    /// Fixes known problems and returns a description of each applied change
+    fn fix(&mut self) -> Vec<String>;
 }
 
 #[allow(unused_variables)]
 #[allow(clippy::needless_pass_by_value)]
-pub fn check_file(file: &Path, password: Option<String>) -> Result<Vec<CheckNotice>, CheckNotice> {
-    let mut result = match file.extension() {
+pub fn check_file(
+    file: &Path,
+    password: Option<String>,
+    config: &CheckConfig,
+    library: Option<&Library>,
+) -> Result<Vec<CheckNotice>, CheckNotice> {
+    let result = match file.extension() {
         Some(ex) => match ex.to_str() {
             #[cfg(feature = "unzip-osb")]
             Some("osb") => {
@@ -164,9 +388,11 @@ pub fn check_file(file: &Path, password: Option<String>) -> Result<Vec<CheckNoti
                 osb::check_file(
                     file,
                     &password.unwrap_or_else(|| deobfuscate(env!("OSB_KEY").trim())),
+                    config,
+                    library,
                 )
             }
-            Some("osc") => osc::check_file(file),
+            Some("osc") => osc::check_file(file, config, library),
             _ => Err(CheckNotice::Error {
                 description: "Keine prüfbare Datei".to_string(),
                 line: None,
@@ -179,35 +405,233 @@ pub fn check_file(file: &Path, password: Option<String>) -> Result<Vec<CheckNoti
             });
         }
     };
+    let mut result = config.apply(result);
     result.sort_by_key(CheckNotice::variant_order);
     Ok(result)
 }
 
-pub fn print() {
-    struct Problem<'a> {
-        code: &'a str,
-        name: &'a str,
-        description: &'a str,
-        fixable: bool,
-    }
+/// Known problem detected by checks
+pub struct Problem {
+    pub code: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub fixable: bool,
+    pub severity: Severity,
+}
 
-    impl Display for Problem<'_> {
-        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            write!(
-                f,
-                "{} {} {}\n\n{}",
-                style(self.code).bold(),
-                style(self.name).underlined(),
-                if self.fixable {
-                    style("(Behebbar)").green()
-                } else {
-                    style("(Nicht behebbar)").red()
-                },
-                self.description
-            )
-        }
+impl Display for Problem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {}\n\n{}",
+            style(self.code).bold(),
+            style(self.name).underlined(),
+            if self.fixable {
+                style("(Behebbar)").green()
+            } else {
+                style("(Nicht behebbar)").red()
+            },
+            self.description
+        )
     }
+}
+
+pub static PROBLEMS: [Problem; 16] = [
+    Problem {
+        code: "2023-0001",
+        name: "Unterformular mit Markierung 'hat Unterformulare'",
+        description: "  Aktuell gibt es keine Unterformulare in Unterformularen, daher\n  \
+        sollte dies nicht vorkommen.\n\n  \
+        Eine mögliche Ursache ist die Speicherung eines Unterformulars als Formular.\n\n  \
+        Das Problem wird beim Verwenden des Unterbefehls 'modify' mit der Option '--fix'\n  \
+        behoben, sofern das Unterformular keine Unterformulare enthält.",
+        fixable: true,
+        severity: Severity::Error,
+    },
+    Problem {
+        code: "2023-0002",
+        name: "Formular hat keine Angabe zum Prozedurdatum",
+        description: "  Formulare benötigen die Angabe des Prozedurdatums, anderenfalls\n  \
+        führt dies zu Problemen in Onkostar.\n\n  \
+        Unterformulare können ein Prozedurdatum haben, müssen es aber nicht.\n\n  \
+        Eine mögliche Ursache ist die Speicherung eines Formulars als Unterformular.",
+        fixable: false,
+        severity: Severity::Error,
+    },
+    Problem {
+        code: "2023-0003",
+        name: "Leerzeichen am Ende der Plausibilitätsregel-Bezeichnung (OSTARSUPP-13334)",
+        description: "  Treten Leerzeichen am Ende der Plausibilitätsregel-Bezeichnung auf,\n  \
+        führt dies zu Fehlern beim Import der OSC-Datei.\n\n  \
+        Das Problem wird beim Verwenden des Unterbefehls 'modify' mit der Option '--fix'\n  \
+        behoben und Leerzeichen entfernt.
+        ",
+        fixable: true,
+        severity: Severity::Error,
+    },
+    Problem {
+        code: "2023-0004",
+        name: "Verweis auf noch nicht definiertes Formular (OSTARSUPP-13212)",
+        description: "  Wenn ein Formular einen Verweis auf ein anderes Formular enthält,\n  \
+        das nicht vor diesem Formular in der OSC-Datei definiert ist, wird der\n  \
+        Formularverweis beim Import der OSC-Datei nicht übernommen.\n\n  \
+        Dies kann bei wechselseitiger Abhängigkeit zwischen zwei (Unter-)Formularen\n  \
+        auftreten.\n\n  \
+        In diesem Fall kann ein erneuter/zweiter Import helfen, da das Onkostar in\n  \
+        diesem Fall alle Formulare importiert hat und der Formularverweis dann \n  \
+        gespeichert werden kann.\n\n  \
+        Das Problem wird beim Verwenden des Unterbefehls 'modify' mit der Option '--fix'\n  \
+        durch Sortierung der Formulare behoben, sofern keine wechselseitige Abhängigkeit\n  \
+        besteht.
+        ",
+        fixable: true,
+        severity: Severity::Error,
+    },
+    Problem {
+        code: "2024-0005",
+        name: "Formular hat Formularverweise ohne Angabe des Formulars in den Formularfeldern",
+        description: "  Formularverweise ohne Angabe des Formulars führen zu Problemen\n  \
+        bei der Verwendung und Darstellung des entsprechenden Formularverweises.\n\n  \
+        Dieses Problem muss manuell behoben werden.
+        ",
+        fixable: false,
+        severity: Severity::Error,
+    },
+    Problem {
+        code: "2026-0006",
+        name: "Formularverweise erst ab Onkostar 2.14.0 verwendbar",
+        description: "  Formularverweise, die nur in der neuen Form mit Angabe mehrerer\n  \
+        möglicher Formulare gespeichert sind, funktionieren erst in Onkostar-Versionen\n  \
+        ab 2.14.0.\n\n  \
+        Bei Verwendung einer älteren Onkostar-Version muss das Formular im\n  \
+        Formularverweis manuell angegeben werden.
+        ",
+        fixable: false,
+        severity: Severity::Info,
+    },
+    Problem {
+        code: "2026-0007",
+        name: "Mehrfach verwendete GUID",
+        description: "  Merkmalskataloge, Datenkataloge, Formulare und deren Einträge werden\n  \
+        beim Import anhand ihrer GUID zugeordnet. Eine mehrfach verwendete GUID führt\n  \
+        dazu, dass Inhalte beim Import überschrieben werden.\n\n  \
+        Eine mögliche Ursache ist das manuelle Kopieren von Inhalten zwischen OSC-Dateien.\n\n  \
+        Dieses Problem muss manuell behoben werden.
+        ",
+        fixable: false,
+        severity: Severity::Error,
+    },
+    Problem {
+        code: "2026-0008",
+        name: "Mehrfach verwendeter Name ohne Beachtung der Groß-/Kleinschreibung",
+        description: "  Formulare und Kataloge werden ohne Beachtung der Groß-/Kleinschreibung\n  \
+        anhand ihres Namens gefunden. Namen, die sich nur in der Groß-/Kleinschreibung\n  \
+        unterscheiden, führen dazu, dass Verweise auf den falschen Inhalt zeigen können.\n\n  \
+        Dies gilt ebenso für Einträge innerhalb eines Datenkatalogs oder Formulars.\n\n  \
+        Dieses Problem muss manuell behoben werden.
+        ",
+        fixable: false,
+        severity: Severity::Error,
+    },
+    Problem {
+        code: "2026-0009",
+        name: "Formularfeld verweist auf nicht vorhandenen Datenkatalogeintrag",
+        description:
+            "  Formularfelder speichern ihre Werte in einem Eintrag eines Datenkatalogs,\n  \
+        der im Formular angegeben ist. Ist dieser Eintrag nicht im Datenkatalog vorhanden,\n  \
+        kann der Wert des Formularfelds nicht gespeichert werden.\n\n  \
+        Ist der Datenkatalog nicht in der OSC-Datei enthalten, kann der Verweis nicht\n  \
+        geprüft werden und es wird eine Information ausgegeben.\n\n  \
+        Dieses Problem muss manuell behoben werden.
+        ",
+        fixable: false,
+        severity: Severity::Error,
+    },
+    Problem {
+        code: "2026-0010",
+        name: "Nicht verwendeter Datenkatalog im Formular",
+        description: "  Ein im Formular angegebener Datenkatalog wird von keinem Formularfeld\n  \
+        verwendet.\n\n  \
+        Ist der Datenkatalog nicht in der OSC-Datei enthalten, wird eine Information\n  \
+        ausgegeben.
+        ",
+        fixable: false,
+        severity: Severity::Warning,
+    },
+    Problem {
+        code: "2026-0011",
+        name: "Verweis auf nicht vorhandenes Formularfeld",
+        description: "  Filter und Plausibilitätsregeln verwenden andere Formularfelder des\n  \
+        Formulars. Ist ein verwendetes Formularfeld nicht vorhanden, z.B. nach dem\n  \
+        Umbenennen oder Entfernen, funktionieren Filter und Plausibilitätsregeln nicht mehr.\n\n  \
+        Dieses Problem muss manuell behoben werden.
+        ",
+        fixable: false,
+        severity: Severity::Error,
+    },
+    Problem {
+        code: "2026-0012",
+        name: "Filter verwendet ausgeblendetes Formularfeld",
+        description: "  Ein Filter verwendet ein Formularfeld, das z.B. durch ein Profil mit\n  \
+        'hide' dauerhaft ausgeblendet ist. Der Wert dieses Formularfelds wird nicht\n  \
+        gespeichert, daher ist der Filter wirkungslos.
+        ",
+        fixable: false,
+        severity: Severity::Warning,
+    },
+    Problem {
+        code: "2026-0013",
+        name: "Script verwendet unbekanntes Formularfeld",
+        description:
+            "  Scripts des Formulars oder der Formularfelder verwenden in Aufrufen von\n  \
+        'getFieldValue()' und 'setFieldValue()' Namen von Formularfeldern, die im Formular\n  \
+        nicht vorhanden sind. Bei 'getFieldValue('verweis').Feld' wird das Feld im\n  \
+        verwiesenen Formular gesucht, sofern dieses in der OSC-Datei enthalten ist.\n\n  \
+        Eine mögliche Ursache ist ein durch ein Profil eingefügtes Script, das für eine\n  \
+        andere Variante des Formulars erstellt wurde.\n\n  \
+        Dieses Problem muss manuell behoben werden.
+        ",
+        fixable: false,
+        severity: Severity::Warning,
+    },
+    Problem {
+        code: "2026-0014",
+        name: "Abhängigkeit in keiner Datei der Bibliothek enthalten",
+        description: "  Ein Katalog oder Formular benötigt einen Katalog oder ein Formular, das\n  \
+        weder in der OSC-Datei noch in einer Datei der mit '--library' angegebenen\n  \
+        Bibliothek enthalten ist. Ein Import der OSC-Datei ist dann nicht vollständig möglich.\n\n  \
+        Dieser Check wird nur bei Angabe einer Bibliothek ausgeführt.
+        ",
+        fixable: false,
+        severity: Severity::Error,
+    },
+    Problem {
+        code: "2026-0015",
+        name: "Funktion in Onkostar-Zielversion nicht verfügbar",
+        description: "  Die Datei verwendet Funktionen, die erst in einer neueren Onkostar-Version\n  \
+        als der mit '--target-version' angegebenen Zielversion verfügbar sind. Diese\n  \
+        Funktionen werden beim Import nicht übernommen oder führen zu Fehlern.\n\n  \
+        Dieser Check wird nur bei Angabe einer Zielversion ausgeführt und ersetzt dann\n  \
+        den Hinweis 2026-0006.
+        ",
+        fixable: false,
+        severity: Severity::Error,
+    },
+    Problem {
+        code: "2026-0016",
+        name: "Datei mit neuerer Onkostar-Version erstellt",
+        description: "  Die Datei wurde mit einer neueren Onkostar-Version als der mit\n  \
+        '--target-version' angegebenen Zielversion erstellt. Auch ohne erkannte nicht\n  \
+        verfügbare Funktionen ist ein Import in die Zielversion möglicherweise nicht\n  \
+        vollständig möglich.\n\n  \
+        Dieser Check wird nur bei Angabe einer Zielversion ausgeführt.
+        ",
+        fixable: false,
+        severity: Severity::Warning,
+    },
+];
 
+pub fn print(config: &CheckConfig, config_file: Option<&str>) {
     println!(
         "{}",
         style("Die folgenden Probleme sind bekannt\n")
@@ -215,60 +639,32 @@ pub fn print() {
             .bold()
     );
 
-    for problem in [
-        Problem {
-            code: "2023-0001",
-            name: "Unterformular mit Markierung 'hat Unterformulare'",
-            description: "  Aktuell gibt es keine Unterformulare in Unterformularen, daher\n  \
-            sollte dies nicht vorkommen.\n\n  \
-            Eine mögliche Ursache ist die Speicherung eines Unterformulars als Formular.",
-            fixable: false,
-        },
-        Problem {
-            code: "2023-0002",
-            name: "Formular hat keine Angabe zum Prozedurdatum",
-            description: "  Formulare benötigen die Angabe des Prozedurdatums, anderenfalls\n  \
-            führt dies zu Problemen in Onkostar.\n\n  \
-            Unterformulare können ein Prozedurdatum haben, müssen es aber nicht.\n\n  \
-            Eine mögliche Ursache ist die Speicherung eines Formulars als Unterformular.",
-            fixable: false,
-        },
-        Problem {
-            code: "2023-0003",
-            name: "Leerzeichen am Ende der Plausibilitätsregel-Bezeichnung (OSTARSUPP-13334)",
-            description:
-                "  Treten Leerzeichen am Ende der Plausibilitätsregel-Bezeichnung auf,\n  \
-            führt dies zu Fehlern beim Import der OSC-Datei.\n\n  \
-            Das Problem wird beim Verwenden des Unterbefehls 'modify' automatisch\n  \
-            behoben und Leerzeichen entfernt.
-            ",
-            fixable: true,
-        },
-        Problem {
-            code: "2023-0004",
-            name: "Verweis auf noch nicht definiertes Formular (OSTARSUPP-13212)",
-            description: "  Wenn ein Formular einen Verweis auf ein anderes Formular enthält,\n  \
-            das nicht vor diesem Formular in der OSC-Datei definiert ist, wird der\n  \
-            Formularverweis beim Import der OSC-Datei nicht übernommen.\n\n  \
-            Dies kann bei wechselseitiger Abhängigkeit zwischen zwei (Unter-)Formularen\n  \
-            auftreten.\n\n  \
-            In diesem Fall kann ein erneuter/zweiter Import helfen, da das Onkostar in\n  \
-            diesem Fall alle Formulare importiert hat und der Formularverweis dann \n  \
-            gespeichert werden kann.
-            ",
-            fixable: false,
-        },
-        Problem {
-            code: "2024-0005",
-            name: "Formular hat Formularverweise ohne Angabe des Formulars in den Formularfeldern",
-            description: "  Formularverweise ohne Angabe des Formulars führen zu Problemen\n  \
-            bei der Verwendung und Darstellung des entsprechenden Formularverweises.\n\n  \
-            Dieses Problem muss manuell behoben werden.
-            ",
-            fixable: false,
-        },
-    ] {
+    for problem in &PROBLEMS {
         println!("{problem}\n");
+        if config.is_disabled(problem.code) {
+            println!("  {}\n", style("Deaktiviert").red());
+        } else {
+            println!("  Schweregrad: {}\n", config.severity_of(problem));
+        }
+    }
+
+    if let Some(ref target_version) = config.target_version {
+        println!("Zielversion: Onkostar {}", style(target_version).yellow());
+    }
+
+    if let Some(config_file) = config_file {
+        println!("Konfiguration aus '{}'", style(config_file).yellow());
+        if !config.ignore_forms.is_empty() {
+            println!(
+                "Ignorierte Formulare: {}",
+                config
+                    .ignore_forms
+                    .iter()
+                    .map(|pattern| format!("'{pattern}'"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
     }
 }
 
@@ -318,15 +714,15 @@ impl Checkable for OnkostarEditor {
             form.get_required_entries(self)
                 .iter()
                 .for_each(|entry| match entry {
-                    Requirement::DataFormReference(item) => {
-                        if !requirement_checked_forms.contains(&item.get_name()) {
-                            result.push(requirement_error(form, *item, "Formular"));
-                        }
+                    Requirement::DataFormReference(item)
+                        if !requirement_checked_forms.contains(&item.get_name()) =>
+                    {
+                        result.push(requirement_error(form, *item, "Formular"));
                     }
-                    Requirement::UnterformularReference(item) => {
-                        if !requirement_checked_forms.contains(&item.get_name()) {
-                            result.push(requirement_error(form, *item, "Unterformular"));
-                        }
+                    Requirement::UnterformularReference(item)
+                        if !requirement_checked_forms.contains(&item.get_name()) =>
+                    {
+                        result.push(requirement_error(form, *item, "Unterformular"));
                     }
                     _ => {}
                 });
@@ -337,24 +733,433 @@ impl Checkable for OnkostarEditor {
             form.get_required_entries(self)
                 .iter()
                 .for_each(|entry| match entry {
-                    Requirement::DataFormReference(item) => {
-                        if !requirement_checked_forms.contains(&item.get_name()) {
-                            result.push(requirement_error(form, *item, "Formular"));
-                        }
+                    Requirement::DataFormReference(item)
+                        if !requirement_checked_forms.contains(&item.get_name()) =>
+                    {
+                        result.push(requirement_error(form, *item, "Formular"));
                     }
-                    Requirement::UnterformularReference(item) => {
-                        if !requirement_checked_forms.contains(&item.get_name()) {
-                            result.push(requirement_error(form, *item, "Unterformular"));
-                        }
+                    Requirement::UnterformularReference(item)
+                        if !requirement_checked_forms.contains(&item.get_name()) =>
+                    {
+                        result.push(requirement_error(form, *item, "Unterformular"));
                     }
                     _ => {}
                 });
         });
 
+        result.append(&mut duplicate_guid_checks(self));
+        result.append(&mut duplicate_name_checks(self));
+
+        // Check references to data catalogue entries
+
+        for form in &self.editor.data_form {
+            result.append(&mut data_catalogue_checks(self, form));
+        }
+        for form in &self.editor.unterformular {
+            result.append(&mut data_catalogue_checks(self, form));
+        }
+
+        // Check field names used in scripts
+
+        for form in &self.editor.data_form {
+            result.append(&mut script_checks(self, form));
+        }
+        for form in &self.editor.unterformular {
+            result.append(&mut script_checks(self, form));
+        }
+
         result
     }
 }
 
+/// Checks that all requirements not contained in the file are provided by a file of the library
+pub fn library_check(data: &OnkostarEditor, library: &Library) -> Vec<CheckNotice> {
+    let editor = &data.editor;
+    let requirements = editor
+        .data_catalogue
+        .iter()
+        .map(|item| (item.get_name(), item.get_required_entries(data)))
+        .chain(
+            editor
+                .data_form
+                .iter()
+                .map(|item| (item.get_name(), item.get_required_entries(data))),
+        )
+        .chain(
+            editor
+                .unterformular
+                .iter()
+                .map(|item| (item.get_name(), item.get_required_entries(data))),
+        );
+
+    let mut missing = BTreeSet::new();
+    for (name, requirements) in requirements {
+        for requirement in &requirements {
+            if let Some((content_type, required_name)) = external_requirement(requirement)
+                && library.find(content_type, required_name).is_empty()
+            {
+                missing.insert((
+                    name.clone(),
+                    content_type.to_string(),
+                    required_name.to_string(),
+                ));
+            }
+        }
+    }
+
+    missing
+        .into_iter()
+        .map(|(name, content_type, required_name)| ErrorWithCode {
+            code: "2026-0014".to_string(),
+            description: format!(
+                "Abhängigkeit {content_type} '{required_name}' von '{name}' ist in keiner Datei der Bibliothek enthalten"
+            ),
+            line: None,
+            example: None,
+        })
+        .collect()
+}
+
+fn duplicated_names(t: &str, names: Vec<String>) -> Vec<CheckNotice> {
+    let mut names_by_key: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for name in names {
+        names_by_key
+            .entry(name.to_lowercase())
+            .or_default()
+            .push(name);
+    }
+
+    names_by_key
+        .into_values()
+        .filter(|names| names.len() > 1)
+        .map(|names| ErrorWithCode {
+            code: "2026-0008".to_string(),
+            description: format!(
+                "{t} mit gleichem Namen ohne Beachtung der Groß-/Kleinschreibung: {}",
+                names
+                    .iter()
+                    .map(|name| format!("'{name}'"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            line: None,
+            example: None,
+        })
+        .collect()
+}
+
+/// Checks for GUIDs used more than once
+fn duplicate_guid_checks(data: &OnkostarEditor) -> Vec<CheckNotice> {
+    let editor = &data.editor;
+
+    let mut items = vec![];
+    for catalogue in &editor.property_catalogue {
+        items.push((
+            catalogue.get_guid(),
+            format!("Merkmalskatalog '{}'", catalogue.get_name()),
+        ));
+    }
+    for catalogue in &editor.data_catalogue {
+        items.push((
+            catalogue.get_guid(),
+            format!("Datenkatalog '{}'", catalogue.get_name()),
+        ));
+        for entry in catalogue.entries() {
+            items.push((
+                entry.get_guid(),
+                format!(
+                    "Datenkatalogeintrag '{}' in '{}'",
+                    entry.get_name(),
+                    catalogue.get_name()
+                ),
+            ));
+        }
+    }
+    for form in &editor.data_form {
+        items.push((form.get_guid(), format!("Formular '{}'", form.get_name())));
+        for entry in form.entries() {
+            items.push((
+                entry.guid.clone(),
+                format!(
+                    "Formularfeld '{}' in '{}'",
+                    entry.get_name(),
+                    form.get_name()
+                ),
+            ));
+        }
+    }
+    for form in &editor.unterformular {
+        items.push((
+            form.get_guid(),
+            format!("Unterformular '{}'", form.get_name()),
+        ));
+        for entry in form.entries() {
+            items.push((
+                entry.guid.clone(),
+                format!(
+                    "Formularfeld '{}' in '{}'",
+                    entry.get_name(),
+                    form.get_name()
+                ),
+            ));
+        }
+    }
+
+    let mut items_by_guid: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (guid, item) in items {
+        if !guid.is_empty() {
+            items_by_guid.entry(guid).or_default().push(item);
+        }
+    }
+
+    items_by_guid
+        .into_iter()
+        .filter(|(_, items)| items.len() > 1)
+        .map(|(guid, items)| ErrorWithCode {
+            code: "2026-0007".to_string(),
+            description: format!(
+                "GUID '{guid}' wird mehrfach verwendet: {}",
+                items.join(", ")
+            ),
+            line: None,
+            example: None,
+        })
+        .collect()
+}
+
+/// Checks for names only differing in case, since forms and catalogues
+/// are looked up by name ignoring case
+fn duplicate_name_checks(data: &OnkostarEditor) -> Vec<CheckNotice> {
+    let editor = &data.editor;
+
+    let mut result = duplicated_names(
+        "Merkmalskataloge",
+        editor
+            .property_catalogue
+            .iter()
+            .map(Named::get_name)
+            .collect(),
+    );
+    result.append(&mut duplicated_names(
+        "Datenkataloge",
+        editor.data_catalogue.iter().map(Named::get_name).collect(),
+    ));
+    result.append(&mut duplicated_names(
+        "Formulare",
+        editor
+            .data_form
+            .iter()
+            .map(Named::get_name)
+            .chain(editor.unterformular.iter().map(Named::get_name))
+            .collect(),
+    ));
+    for catalogue in &editor.data_catalogue {
+        result.append(&mut duplicated_names(
+            &format!("Einträge in Datenkatalog '{}'", catalogue.get_name()),
+            catalogue.entries().iter().map(Named::get_name).collect(),
+        ));
+    }
+    for form in &editor.data_form {
+        result.append(&mut duplicated_names(
+            &format!("Formularfelder in '{}'", form.get_name()),
+            form.entries().iter().map(Named::get_name).collect(),
+        ));
+    }
+    for form in &editor.unterformular {
+        result.append(&mut duplicated_names(
+            &format!("Formularfelder in '{}'", form.get_name()),
+            form.entries().iter().map(Named::get_name).collect(),
+        ));
+    }
+
+    result
+}
+
+/// Checks that all form fields reference existing entries in the data catalogues of the form
+/// and that all data catalogues of the form are used.
+/// Data catalogues not contained in the file cannot be checked and are reported as info.
+fn data_catalogue_checks<T>(data: &OnkostarEditor, form: &Form<T>) -> Vec<CheckNotice> {
+    let mut result = vec![];
+    let mut used_catalogues = BTreeSet::new();
+
+    for entry in form.entries() {
+        let Some(entry_name) = entry.data_catalogue_entry() else {
+            continue;
+        };
+
+        // The table might also name the property catalogue of the entry,
+        // in this case all data catalogues of the form are searched
+        let catalogue_names = match entry.data_catalogue_entry_table() {
+            Some(table) if data.find_property_catalogue(table).is_none() => vec![table],
+            _ => form.data_catalogues().iter().map(String::as_str).collect(),
+        };
+
+        let mut external_catalogues = vec![];
+        let mut found = false;
+        for catalogue_name in catalogue_names {
+            match data.find_data_catalogue(catalogue_name) {
+                Some(catalogue) => {
+                    if catalogue
+                        .entries()
+                        .iter()
+                        .any(|catalogue_entry| catalogue_entry.get_name() == entry_name)
+                    {
+                        used_catalogues.insert(catalogue_name.to_lowercase());
+                        found = true;
+                        break;
+                    }
+                }
+                None => external_catalogues.push(catalogue_name),
+            }
+        }
+
+        if found {
+            continue;
+        }
+
+        if external_catalogues.is_empty() {
+            result.push(ErrorWithCode {
+                code: "2026-0009".to_string(),
+                description: format!(
+                    "Formularfeld '{}' in '{}' verweist auf nicht vorhandenen Datenkatalogeintrag '{entry_name}'",
+                    entry.get_name(),
+                    form.get_name()
+                ),
+                line: None,
+                example: None,
+            });
+        } else {
+            for catalogue_name in &external_catalogues {
+                used_catalogues.insert(catalogue_name.to_lowercase());
+            }
+            result.push(InfoWithCode {
+                code: "2026-0009".to_string(),
+                description: format!(
+                    "Datenkatalogeintrag '{entry_name}' von Formularfeld '{}' in '{}' nicht prüfbar, da Datenkatalog nicht enthalten: {}",
+                    entry.get_name(),
+                    form.get_name(),
+                    external_catalogues
+                        .iter()
+                        .map(|name| format!("'{name}'"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                line: None,
+                example: None,
+            });
+        }
+    }
+
+    for catalogue_name in form.data_catalogues() {
+        if used_catalogues.contains(&catalogue_name.to_lowercase()) {
+            continue;
+        }
+        let description = format!(
+            "Datenkatalog '{catalogue_name}' in '{}' wird von keinem Formularfeld verwendet",
+            form.get_name()
+        );
+        result.push(if data.find_data_catalogue(catalogue_name).is_some() {
+            WarningWithCode {
+                code: "2026-0010".to_string(),
+                description,
+                line: None,
+                example: None,
+            }
+        } else {
+            InfoWithCode {
+                code: "2026-0010".to_string(),
+                description,
+                line: None,
+                example: None,
+            }
+        });
+    }
+
+    result
+}
+
+/// Calls of `getFieldValue()` and `setFieldValue()` using a string literal as field name,
+/// optionally followed by the name of a field in a referenced form
+#[allow(clippy::expect_used)]
+static FIELD_VALUE_CALL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\b[gs]etFieldValue\s*\(\s*(?:'([^']*)'|"([^"]*)")(?:\s*\)\s*\.\s*(\w+))?"#)
+        .expect("Invalid regex")
+});
+
+/// Returns all forms referenced by the given form field or `None`,
+/// if not all of the referenced forms are contained in the file
+fn referenced_forms<'a>(
+    data: &'a OnkostarEditor,
+    entry: &Entry,
+) -> Option<Vec<&'a Form<DataFormType>>> {
+    entry
+        .referenced_data_form
+        .iter()
+        .cloned()
+        .chain(
+            entry
+                .data_form_references
+                .iter()
+                .flatten()
+                .flat_map(|references| &references.referenced_data_form)
+                .map(Named::get_name),
+        )
+        .map(|name| data.find_data_form(&name))
+        .collect()
+}
+
+/// Checks that scripts of the form only use existing form fields in calls of
+/// `getFieldValue()` and `setFieldValue()`
+fn script_checks<T>(data: &OnkostarEditor, form: &Form<T>) -> Vec<CheckNotice> {
+    let entries = form.entries();
+    let find_entry = |name: &str| entries.iter().find(|entry| entry.get_name() == name);
+
+    let mut result = vec![];
+
+    for (location, script) in form.scripts() {
+        let mut unknown_fields = BTreeSet::new();
+
+        for captures in FIELD_VALUE_CALL.captures_iter(script.code()) {
+            let Some(field_name) = captures.get(1).or(captures.get(2)).map(|m| m.as_str()) else {
+                continue;
+            };
+
+            let Some(entry) = find_entry(field_name) else {
+                unknown_fields.insert(format!("'{field_name}'"));
+                continue;
+            };
+
+            if let Some(referenced_field) = captures.get(3).map(|m| m.as_str())
+                && let Some(forms) = referenced_forms(data, entry)
+                && !forms.is_empty()
+                && !forms.iter().any(|referenced_form| {
+                    referenced_form
+                        .entries()
+                        .iter()
+                        .any(|referenced_entry| referenced_entry.get_name() == referenced_field)
+                })
+            {
+                unknown_fields.insert(format!("'{field_name}.{referenced_field}'"));
+            }
+        }
+
+        if !unknown_fields.is_empty() {
+            result.push(WarningWithCode {
+                code: "2026-0013".to_string(),
+                description: format!(
+                    "Script in {location} von '{}' verwendet unbekannte Formularfelder: {}",
+                    form.get_name(),
+                    unknown_fields.into_iter().collect::<Vec<_>>().join(", ")
+                ),
+                line: None,
+                example: None,
+            });
+        }
+    }
+
+    result
+}
+
 fn common_check<T>(form: &Form<T>) -> Vec<CheckNotice> {
     let missing_forms_in_refs = match form.entries {
         Some(ref entries) => entries
@@ -395,14 +1200,71 @@ fn common_check<T>(form: &Form<T>) -> Vec<CheckNotice> {
         });
     }
 
-    if missing_forms_in_refs.is_empty() && !missing_forms_in_refs_legacy.is_empty() {
-        result.push(Info {
-            description: format!(
-                "Formular '{}' hat Formularverweise, die erst in neueren Onkostar-Versionen ab 2.14.0 funktionieren",
-                form.get_name()
-            ),
-            line: None,
-        });
+    result.append(&mut field_reference_check(form));
+
+    result
+}
+
+/// Checks that filters and plausibility rules only use existing form fields
+/// and that filters do not depend on hidden form fields
+fn field_reference_check<T>(form: &Form<T>) -> Vec<CheckNotice> {
+    let entries = form.entries();
+    let find_entry = |name: &str| entries.iter().find(|entry| entry.get_name() == name);
+
+    let mut result = vec![];
+
+    for entry in entries {
+        let Some(ref filter) = entry.filter else {
+            continue;
+        };
+        for ref_entry in filter.ref_entries() {
+            match find_entry(ref_entry) {
+                None => result.push(ErrorWithCode {
+                    code: "2026-0011".to_string(),
+                    description: format!(
+                        "Filter von Formularfeld '{}' in '{}' verweist auf nicht vorhandenes Formularfeld '{ref_entry}'",
+                        entry.get_name(),
+                        form.get_name()
+                    ),
+                    line: None,
+                    example: None,
+                }),
+                Some(referenced)
+                    if referenced
+                        .filter
+                        .as_ref()
+                        .is_some_and(Filter::is_always_false) =>
+                {
+                    result.push(WarningWithCode {
+                        code: "2026-0012".to_string(),
+                        description: format!(
+                            "Filter von Formularfeld '{}' in '{}' verwendet ausgeblendetes Formularfeld '{ref_entry}'",
+                            entry.get_name(),
+                            form.get_name()
+                        ),
+                        line: None,
+                        example: None,
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    for (rule_name, entry_names) in form.plausibility_rule_entry_names() {
+        for entry_name in entry_names {
+            if find_entry(entry_name).is_none() {
+                result.push(ErrorWithCode {
+                    code: "2026-0011".to_string(),
+                    description: format!(
+                        "Plausibilitätsregel '{rule_name}' in '{}' verweist auf nicht vorhandenes Formularfeld '{entry_name}'",
+                        form.get_name()
+                    ),
+                    line: None,
+                    example: None,
+                });
+            }
+        }
     }
 
     result
@@ -462,3 +1324,395 @@ impl Checkable for Form<UnterformularType> {
         result
     }
 }
+
+fn trim_plausibility_rule_names<T>(form: &mut Form<T>) -> Vec<String> {
+    form.trim_plausibility_rule_names()
+        .into_iter()
+        .map(|name| {
+            format!(
+                "(2023-0003) Leerzeichen am Ende der Plausibilitätsregel-Bezeichnung '{}' in '{}' entfernt",
+                name,
+                form.get_name()
+            )
+        })
+        .collect()
+}
+
+impl Fixable for Form<DataFormType> {
+    fn fix(&mut self) -> Vec<String> {
+        trim_plausibility_rule_names(self)
+    }
+}
+
+impl Fixable for Form<UnterformularType> {
+    fn fix(&mut self) -> Vec<String> {
+        let mut result = trim_plausibility_rule_names(self);
+
+        let has_subforms = self
+            .entries
+            .as_ref()
+            .is_some_and(|entries| entries.entry.iter().any(TypedEntry::is_subform));
+
+        if self.hat_unterformulare && !has_subforms {
+            self.hat_unterformulare = false;
+            result.push(format!(
+                "(2023-0001) Markierung 'hat Unterformulare' in Unterformular '{}' entfernt",
+                self.get_name()
+            ));
+        }
+
+        result
+    }
+}
+
+impl Fixable for OnkostarEditor {
+    fn fix(&mut self) -> Vec<String> {
+        let mut result = self
+            .editor
+            .data_form
+            .iter_mut()
+            .flat_map(Form::fix)
+            .collect::<Vec<_>>();
+
+        result.extend(self.editor.unterformular.iter_mut().flat_map(Form::fix));
+
+        let names = |editor: &OnkostarEditor| {
+            editor
+                .editor
+                .unterformular
+                .iter()
+                .map(Named::get_name)
+                .chain(editor.editor.data_form.iter().map(Named::get_name))
+                .collect::<Vec<_>>()
+        };
+
+        let before = names(self);
+        let cycles = self.sort_forms_by_requirements();
+        if names(self) != before {
+            result.push(
+                "(2023-0004) Formulare so sortiert, dass verwendete Formulare zuvor definiert sind"
+                    .to_string(),
+            );
+        }
+        for cycle in cycles {
+            result.push(format!(
+                "(2023-0004) Gegenseitige Abhängigkeit der Formulare {} nicht behebbar - Import in mehreren Durchgängen erforderlich",
+                cycle
+                    .iter()
+                    .map(|name| format!("'{name}'"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        result
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use model::osc::onkostar_editor::OnkostarEditor;
+    use model::profile::Profile;
+
+    use crate::checks::config::CheckConfig;
+    use crate::checks::{CheckNotice, Checkable, Fixable, Severity, library_check, osc};
+    use crate::library::Library;
+
+    fn codes(onkostar_editor: &OnkostarEditor) -> Vec<String> {
+        onkostar_editor
+            .check()
+            .into_iter()
+            .filter_map(|notice| match notice {
+                CheckNotice::ErrorWithCode { code, .. } => Some(code),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_compare_severity_of_notices() {
+        let notices = [
+            CheckNotice::ErrorWithCode {
+                code: "2023-0001".to_string(),
+                description: "Test".to_string(),
+                line: None,
+                example: None,
+            },
+            CheckNotice::Error {
+                description: "Test".to_string(),
+                line: None,
+            },
+            CheckNotice::WarningWithCode {
+                code: "2023-0001".to_string(),
+                description: "Test".to_string(),
+                line: None,
+                example: None,
+            },
+            CheckNotice::Warning {
+                description: "Test".to_string(),
+                line: None,
+            },
+            CheckNotice::InfoWithCode {
+                code: "2023-0001".to_string(),
+                description: "Test".to_string(),
+                line: None,
+                example: None,
+            },
+            CheckNotice::Info {
+                description: "Test".to_string(),
+                line: None,
+            },
+            CheckNotice::Ok("Test".to_string()),
+            CheckNotice::Progress {
+                description: "Test".to_string(),
+                severity: Severity::Error,
+            },
+        ];
+
+        let at_least = |severity: Severity| {
+            notices
+                .iter()
+                .map(|notice| notice.is_at_least(severity))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            at_least(Severity::Error),
+            vec![true, true, false, false, false, false, false, false]
+        );
+        assert_eq!(
+            at_least(Severity::Warning),
+            vec![true, true, true, true, false, false, false, false]
+        );
+        assert_eq!(
+            at_least(Severity::Info),
+            vec![true, true, true, true, true, true, false, false]
+        );
+    }
+
+    #[test]
+    fn should_not_report_any_notice_for_clean_file() {
+        // Test file without forms
+        let lines = include_str!("../../libs/model/tests/test.osc")
+            .lines()
+            .collect::<Vec<_>>();
+        let content = [&lines[..198], &lines[787..]].concat().join("\n");
+
+        let notices = osc::check(&content, &CheckConfig::default(), None).unwrap();
+
+        assert!(notices.is_empty());
+        assert!(
+            !notices
+                .into_iter()
+                .chain([CheckNotice::Progress {
+                    description: "Prüfe Eintrag 'test.osc'".to_string(),
+                    severity: Severity::Info,
+                }])
+                .any(|notice| notice.is_at_least(Severity::Info))
+        );
+    }
+
+    #[test]
+    fn should_fix_subform_with_subform_flag() {
+        let mut onkostar_editor =
+            OnkostarEditor::from_str(&include_str!("../../libs/model/tests/test.osc").replacen(
+                "<hatUnterformulare>false</hatUnterformulare>",
+                "<hatUnterformulare>true</hatUnterformulare>",
+                1,
+            ))
+            .unwrap();
+        assert!(codes(&onkostar_editor).contains(&"2023-0001".to_string()));
+
+        let fixes = onkostar_editor.fix();
+
+        assert_eq!(fixes.len(), 1);
+        assert!(fixes[0].starts_with("(2023-0001)"));
+        assert!(!codes(&onkostar_editor).contains(&"2023-0001".to_string()));
+    }
+
+    #[test]
+    fn should_report_duplicated_guids() {
+        let onkostar_editor =
+            OnkostarEditor::from_str(include_str!("../../libs/model/tests/test.osc")).unwrap();
+
+        let notices = onkostar_editor.check();
+        let duplicated_guids = notices
+            .iter()
+            .filter(|notice| notice.code() == Some("2026-0007"))
+            .collect::<Vec<_>>();
+
+        assert_eq!(duplicated_guids.len(), 1);
+        assert!(
+            duplicated_guids[0]
+                .to_string()
+                .contains("317f11b6-41be-4d80-abf6-78d7100f2f58")
+        );
+    }
+
+    #[test]
+    fn should_report_names_duplicated_ignoring_case() {
+        let onkostar_editor =
+            OnkostarEditor::from_str(&include_str!("../../libs/model/tests/test.osc").replacen(
+                "<Name>Unterformular</Name>",
+                "<Name>HAUPTFORMULAR</Name>",
+                1,
+            ))
+            .unwrap();
+
+        assert!(codes(&onkostar_editor).contains(&"2026-0008".to_string()));
+        assert!(
+            !codes(
+                &OnkostarEditor::from_str(include_str!("../../libs/model/tests/test.osc")).unwrap()
+            )
+            .contains(&"2026-0008".to_string())
+        );
+    }
+
+    #[test]
+    fn should_report_missing_data_catalogue_entries() {
+        let onkostar_editor =
+            OnkostarEditor::from_str(&include_str!("../../libs/model/tests/test.osc").replacen(
+                "<DataCatalogueEntry>Termin</DataCatalogueEntry>",
+                "<DataCatalogueEntry>Unbekannt</DataCatalogueEntry>",
+                1,
+            ))
+            .unwrap();
+
+        let notices = onkostar_editor.check();
+
+        assert!(codes(&onkostar_editor).contains(&"2026-0009".to_string()));
+        assert!(notices.iter().any(|notice| matches!(
+            notice,
+            CheckNotice::WarningWithCode { code, description, .. }
+                if code == "2026-0010" && description.contains("'Unterformulardaten'")
+        )));
+    }
+
+    #[test]
+    fn should_report_entries_of_external_data_catalogues_as_info() {
+        let onkostar_editor =
+            OnkostarEditor::from_str(&include_str!("../../libs/model/tests/test.osc").replacen(
+                "<DataCatalogue>Hauptformulardaten</DataCatalogue>",
+                "<DataCatalogue>Extern</DataCatalogue>",
+                1,
+            ))
+            .unwrap();
+
+        let notices = onkostar_editor.check();
+
+        assert!(!codes(&onkostar_editor).contains(&"2026-0009".to_string()));
+        assert!(notices.iter().any(|notice| matches!(
+            notice,
+            CheckNotice::InfoWithCode { code, description, .. }
+                if code == "2026-0009" && description.contains("'Extern'")
+        )));
+    }
+
+    #[test]
+    fn should_report_filters_with_missing_fields() {
+        let onkostar_editor =
+            OnkostarEditor::from_str(&include_str!("../../libs/model/tests/test.osc").replacen(
+                "<RefEntries/>",
+                "<RefEntries><RefEntry>Unbekannt</RefEntry></RefEntries>",
+                1,
+            ))
+            .unwrap();
+
+        assert!(codes(&onkostar_editor).contains(&"2026-0011".to_string()));
+    }
+
+    #[test]
+    fn should_report_filters_using_hidden_fields() {
+        let mut onkostar_editor =
+            OnkostarEditor::from_str(&include_str!("../../libs/model/tests/test.osc").replace(
+                "<RefEntries/>",
+                "<RefEntries><RefEntry>Button1</RefEntry></RefEntries>",
+            ))
+            .unwrap();
+        let profile = Profile::from_str(
+            "
+            forms:
+              - name: 'Hauptformular'
+                form_fields:
+                  - name: Button1
+                    hide: true
+            ",
+        )
+        .unwrap();
+
+        assert!(
+            !onkostar_editor
+                .check()
+                .iter()
+                .any(|notice| notice.code() == Some("2026-0012"))
+        );
+
+        onkostar_editor.apply_profile(&profile);
+
+        assert!(onkostar_editor.check().iter().any(|notice| matches!(
+            notice,
+            CheckNotice::WarningWithCode { code, description, .. }
+                if code == "2026-0012" && description.contains("'Auswahl'")
+        )));
+    }
+
+    #[test]
+    fn should_report_unknown_fields_in_scripts() {
+        let onkostar_editor =
+            OnkostarEditor::from_str(&include_str!("../../libs/model/tests/test.osc").replacen(
+                "<Code>/* test */</Code>",
+                "<Code>setFieldValue('Auswahl', getFieldValue(\"Unbekannt\"));\
+                 getFieldValue('Formularverweis').Datum;</Code>",
+                1,
+            ))
+            .unwrap();
+
+        let notices = onkostar_editor
+            .check()
+            .into_iter()
+            .filter(|notice| notice.code() == Some("2026-0013"))
+            .collect::<Vec<_>>();
+
+        assert_eq!(notices.len(), 1);
+        assert!(matches!(
+            &notices[0],
+            CheckNotice::WarningWithCode { description, .. }
+                if description.ends_with("unbekannte Formularfelder: 'Unbekannt'")
+        ));
+    }
+
+    #[test]
+    fn should_report_requirements_missing_in_library() {
+        let onkostar_editor =
+            OnkostarEditor::from_str(include_str!("../../libs/model/tests/test.osc")).unwrap();
+        let mut library = Library::default();
+        library.add("test.osc", &onkostar_editor);
+
+        let notices = library_check(&onkostar_editor, &library);
+
+        assert_eq!(notices.len(), 1);
+        assert!(notices[0].to_string().contains("'Anderes Formular'"));
+
+        library.add(
+            "anderes-formular.osc",
+            &OnkostarEditor::from_str(&include_str!("../../libs/model/tests/test.osc").replace(
+                "<Name>Hauptformular</Name>",
+                "<Name>Anderes Formular</Name>",
+            ))
+            .unwrap(),
+        );
+
+        assert!(library_check(&onkostar_editor, &library).is_empty());
+    }
+
+    #[test]
+    fn should_not_report_fixes_without_problems() {
+        let mut onkostar_editor =
+            OnkostarEditor::from_str(include_str!("../../libs/model/tests/test.osc")).unwrap();
+
+        assert!(onkostar_editor.fix().is_empty());
+    }
+}
@@ -24,10 +24,17 @@ use std::path::Path;
 
 use indicatif::{ProgressBar, ProgressStyle};
 
-use crate::checks::{CheckNotice, osc};
+use crate::checks::config::CheckConfig;
+use crate::checks::{CheckNotice, Severity, osc};
+use crate::library::Library;
 
 #[cfg(feature = "unzip-osb")]
-pub fn check_file(file: &Path, password: &str) -> Result<Vec<CheckNotice>, CheckNotice> {
+pub fn check_file(
+    file: &Path,
+    password: &str,
+    config: &CheckConfig,
+    library: Option<&Library>,
+) -> Result<Vec<CheckNotice>, CheckNotice> {
     let file = match fs::File::open(file) {
         Ok(file) => file,
         Err(err) => {
@@ -64,11 +71,11 @@ pub fn check_file(file: &Path, password: &str) -> Result<Vec<CheckNotice>, Check
             if zip_file.is_file() && zip_file.name().to_lowercase().ends_with(".osc") {
                 let mut buf = String::new();
                 let _ = zip_file.read_to_string(&mut buf);
-                match osc::check(&buf) {
-                    Ok(ref mut check_result) => {
-                        result.push(CheckNotice::Info {
+                match osc::check(&buf, config, library) {
+                    Ok(check_result) => {
+                        result.push(CheckNotice::Progress {
                             description: format!("Prüfe Eintrag '{}'", zip_file.name()),
-                            line: None,
+                            severity: Severity::Info,
                         });
                         if check_result.is_empty() {
                             result.push(CheckNotice::Ok(format!(
@@ -76,7 +83,11 @@ pub fn check_file(file: &Path, password: &str) -> Result<Vec<CheckNotice>, Check
                                 zip_file.name()
                             )));
                         }
-                        result.append(check_result);
+                        result.extend(
+                            check_result
+                                .into_iter()
+                                .map(|notice| notice.in_entry(zip_file.name())),
+                        );
                     }
                     Err(_) => result.push(CheckNotice::Warning {
                         description: format!(
@@ -89,12 +100,12 @@ pub fn check_file(file: &Path, password: &str) -> Result<Vec<CheckNotice>, Check
                 continue;
             }
             if zip_file.is_file() {
-                result.push(CheckNotice::Warning {
+                result.push(CheckNotice::Progress {
                     description: format!(
                         "Überspringe Eintrag '{}': Keine OSC-Datei",
                         zip_file.name()
                     ),
-                    line: None,
+                    severity: Severity::Warning,
                 });
             }
         } else {
@@ -19,15 +19,24 @@
  */
 
 use std::fs;
+use std::ops::RangeInclusive;
 use std::path::Path;
-use std::str::FromStr;
 
-use crate::checks::{CheckNotice, Checkable};
+use crate::checks::config::CheckConfig;
+use crate::checks::{CheckNotice, Checkable, compatibility, library_check};
+use crate::library::Library;
 use model::osc::onkostar_editor::OnkostarEditor;
+use quick_xml::Reader;
+use quick_xml::escape::unescape;
+use quick_xml::events::Event;
 
-pub fn check_file(file: &Path) -> Result<Vec<CheckNotice>, CheckNotice> {
+pub fn check_file(
+    file: &Path,
+    config: &CheckConfig,
+    library: Option<&Library>,
+) -> Result<Vec<CheckNotice>, CheckNotice> {
     match fs::read_to_string(file) {
-        Ok(content) => check(&content),
+        Ok(content) => check(&content, config, library),
         _ => Err(CheckNotice::Error {
             description: "Kann Datei nicht lesen".to_string(),
             line: None,
@@ -35,15 +44,43 @@ pub fn check_file(file: &Path) -> Result<Vec<CheckNotice>, CheckNotice> {
     }
 }
 
-pub fn check(content: &str) -> Result<Vec<CheckNotice>, CheckNotice> {
+pub fn check(
+    content: &str,
+    config: &CheckConfig,
+    library: Option<&Library>,
+) -> Result<Vec<CheckNotice>, CheckNotice> {
+    let ignored_lines = ignored_form_lines(content, config);
+    let is_ignored = |line: usize| ignored_lines.iter().any(|lines| lines.contains(&line));
+
     let mut result = content
         .lines()
         .enumerate()
-        .flat_map(|(line, content)| check_line(line, content))
+        .filter(|(index, _)| !is_ignored(index + 1))
+        .flat_map(|(index, content)| check_line(index + 1, content))
         .collect::<Vec<_>>();
 
-    let inner_checks = &mut match OnkostarEditor::from_str(content) {
-        Ok(data) => data.check(),
+    let inner_checks = &mut match OnkostarEditor::from_str_lossless(content) {
+        Ok(mut data) => {
+            config.remove_ignored_forms(&mut data);
+            data.unknown_elements
+                .iter()
+                .filter(|unknown_element| !is_ignored(unknown_element.line))
+                .map(|unknown_element| CheckNotice::Warning {
+                    description: format!(
+                        "Unbekanntes Element '{}' in {}",
+                        unknown_element.name, unknown_element.location
+                    ),
+                    line: Some(unknown_element.line),
+                })
+                .chain(data.check())
+                .chain(compatibility::check(&data, config.target_version.as_ref()))
+                .chain(
+                    library
+                        .map(|library| library_check(&data, library))
+                        .unwrap_or_default(),
+                )
+                .collect::<Vec<_>>()
+        }
         Err(err) => {
             return Err(CheckNotice::Error {
                 description: format!("Interner Fehler: {err}"),
@@ -56,6 +93,63 @@ pub fn check(content: &str) -> Result<Vec<CheckNotice>, CheckNotice> {
     Ok(result)
 }
 
+/// Lines of all forms and subforms to be ignored
+fn ignored_form_lines(content: &str, config: &CheckConfig) -> Vec<RangeInclusive<usize>> {
+    if config.ignore_forms.is_empty() {
+        return vec![];
+    }
+
+    let line = |offset: u64| {
+        let offset = usize::try_from(offset).unwrap_or(usize::MAX);
+        content[..offset.min(content.len())].matches('\n').count() + 1
+    };
+
+    let mut reader = Reader::from_str(content);
+    let mut result = vec![];
+    let mut depth = 0;
+    // Start of the current form and its name
+    let mut form: Option<(u64, String)> = None;
+    let mut in_name = false;
+
+    loop {
+        let start = reader.buffer_position();
+        match reader.read_event() {
+            Ok(Event::Start(element)) => {
+                depth += 1;
+                // Forms are contained in 'OnkostarEditor > Editor'
+                if depth == 3 && matches!(element.name().as_ref(), b"DataForm" | b"Unterformular") {
+                    form = Some((start, String::new()));
+                }
+                in_name = depth == 4 && form.is_some() && element.name().as_ref() == b"Name";
+            }
+            Ok(Event::Text(text)) if in_name => {
+                if let (Some((_, name)), Ok(text)) = (&mut form, text.decode()) {
+                    name.push_str(&text);
+                }
+            }
+            Ok(Event::GeneralRef(reference)) if in_name => {
+                if let (Some((_, name)), Ok(reference)) = (&mut form, reference.decode()) {
+                    name.push_str(&unescape(&format!("&{reference};")).unwrap_or_default());
+                }
+            }
+            Ok(Event::End(_)) => {
+                if depth == 3
+                    && let Some((start, name)) = form.take()
+                    && config.is_ignored_form(name.trim())
+                {
+                    result.push(line(start)..=line(reader.buffer_position()));
+                }
+                in_name = false;
+                depth -= 1;
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+
+    result
+}
+
 fn check_line(line: usize, content: &str) -> Vec<CheckNotice> {
     let mut result = vec![];
 
@@ -72,3 +166,57 @@ fn check_line(line: usize, content: &str) -> Vec<CheckNotice> {
 
     result
 }
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::checks::CheckNotice;
+    use crate::checks::config::CheckConfig;
+    use crate::checks::osc::{check, ignored_form_lines};
+
+    fn content() -> String {
+        include_str!("../../libs/model/tests/test.osc")
+            .replacen(
+                "<Unterformular>\n",
+                "<Unterformular>\n<!-- <Bezeichnung>Regel </Bezeichnung> -->\n",
+                1,
+            )
+            .replacen(
+                "<DataForm>\n",
+                "<DataForm>\n<!-- <Bezeichnung>Regel </Bezeichnung> -->\n",
+                1,
+            )
+    }
+
+    fn line_notices(config: &CheckConfig) -> Vec<usize> {
+        check(&content(), config, None)
+            .unwrap()
+            .iter()
+            .filter_map(|notice| match notice {
+                CheckNotice::ErrorWithCode {
+                    code,
+                    line: Some(line),
+                    ..
+                } if code == "2023-0003" => Some(*line),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_find_lines_of_ignored_forms() {
+        let config = CheckConfig::from_str("ignore_forms: ['Haupt*']").unwrap();
+
+        assert_eq!(ignored_form_lines(&content(), &config), vec![388..=789]);
+    }
+
+    #[test]
+    fn should_not_report_line_notices_of_ignored_forms() {
+        assert_eq!(line_notices(&CheckConfig::default()), vec![200, 389]);
+
+        let config = CheckConfig::from_str("ignore_forms: ['Haupt*']").unwrap();
+        assert_eq!(line_notices(&config), vec![200]);
+    }
+}
@@ -0,0 +1,266 @@
+/*
+ * This file is part of osc-variant
+ *
+ * Copyright (C) 2026 the original author or authors.
+ *
+ * This program is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation; either version 2 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along
+ * with this program; if not, write to the Free Software Foundation, Inc.,
+ * 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
+ */
+
+//! Report of check results in SARIF 2.1.0 format
+
+use serde::Serialize;
+
+use crate::checks::{CheckNotice, PROBLEMS, Problem};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+#[derive(Serialize)]
+struct Sarif {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run>,
+}
+
+#[derive(Serialize)]
+struct Run {
+    tool: Tool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+struct Tool {
+    driver: Driver,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<Rule>,
+}
+
+#[derive(Serialize)]
+struct Text {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    id: &'static str,
+    name: &'static str,
+    short_description: Text,
+    full_description: Text,
+    properties: RuleProperties,
+}
+
+#[derive(Serialize)]
+struct RuleProperties {
+    fixable: bool,
+}
+
+impl From<&Problem> for Rule {
+    fn from(problem: &Problem) -> Self {
+        Rule {
+            id: problem.code,
+            name: problem.name,
+            short_description: Text {
+                text: problem.name.to_string(),
+            },
+            full_description: Text {
+                text: problem
+                    .description
+                    .lines()
+                    .map(str::trim)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+                    .trim()
+                    .to_string(),
+            },
+            properties: RuleProperties {
+                fixable: problem.fixable,
+            },
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_id: Option<String>,
+    level: &'static str,
+    message: Text,
+    locations: Vec<Location>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<Region>,
+}
+
+#[derive(Serialize)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: usize,
+}
+
+/// Creates a SARIF report of all notices found in `file`.
+/// Lines of notices are omitted for OSB files, since they refer to the contained OSC files.
+pub fn to_sarif(file: &str, notices: &[CheckNotice]) -> Result<String, serde_json::Error> {
+    let with_lines = !file.to_lowercase().ends_with(".osb");
+
+    let results = notices
+        .iter()
+        .filter_map(|notice| {
+            let (rule_id, level, description, line) = match notice {
+                CheckNotice::ErrorWithCode {
+                    code,
+                    description,
+                    line,
+                    ..
+                } => (Some(code.clone()), "error", description, line),
+                CheckNotice::WarningWithCode {
+                    code,
+                    description,
+                    line,
+                    ..
+                } => (Some(code.clone()), "warning", description, line),
+                CheckNotice::InfoWithCode {
+                    code,
+                    description,
+                    line,
+                    ..
+                } => (Some(code.clone()), "note", description, line),
+                CheckNotice::Error { description, line } => (None, "error", description, line),
+                CheckNotice::Warning { description, line } => (None, "warning", description, line),
+                CheckNotice::Info { description, line } => (None, "note", description, line),
+                CheckNotice::Ok(_) | CheckNotice::Progress { .. } => return None,
+            };
+
+            Some(SarifResult {
+                rule_id,
+                level,
+                message: Text {
+                    text: description.clone(),
+                },
+                locations: vec![Location {
+                    physical_location: PhysicalLocation {
+                        artifact_location: ArtifactLocation {
+                            uri: file.to_string(),
+                        },
+                        region: line
+                            .filter(|_| with_lines)
+                            .map(|start_line| Region { start_line }),
+                    },
+                }],
+            })
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&Sarif {
+        schema: SCHEMA,
+        version: "2.1.0",
+        runs: vec![Run {
+            tool: Tool {
+                driver: Driver {
+                    name: env!("CARGO_PKG_NAME"),
+                    version: env!("CARGO_PKG_VERSION"),
+                    information_uri: "https://github.com/pcvolkmer/osc-variant",
+                    rules: PROBLEMS.iter().map(Rule::from).collect(),
+                },
+            },
+            results,
+        }],
+    })
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use crate::checks::CheckNotice;
+    use crate::checks::sarif::to_sarif;
+
+    fn notices() -> Vec<CheckNotice> {
+        vec![
+            CheckNotice::ErrorWithCode {
+                code: "2023-0003".to_string(),
+                description: "Leerzeichen am Ende der Plausibilitätsregel-Bezeichnung".to_string(),
+                line: Some(42),
+                example: None,
+            },
+            CheckNotice::Ok("Keine Probleme".to_string()),
+        ]
+    }
+
+    #[test]
+    fn should_contain_rules_of_known_problems() {
+        let sarif: Value =
+            serde_json::from_str(&to_sarif("test.osc", &notices()).unwrap()).unwrap();
+
+        let rule = &sarif["runs"][0]["tool"]["driver"]["rules"][2];
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(rule["id"], "2023-0003");
+        assert_eq!(rule["properties"]["fixable"], true);
+        assert!(
+            !rule["fullDescription"]["text"]
+                .as_str()
+                .unwrap()
+                .starts_with(' ')
+        );
+    }
+
+    #[test]
+    fn should_point_to_line_of_notice() {
+        let sarif: Value =
+            serde_json::from_str(&to_sarif("test.osc", &notices()).unwrap()).unwrap();
+
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["ruleId"], "2023-0003");
+        assert_eq!(results[0]["level"], "error");
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "test.osc");
+        assert_eq!(location["region"]["startLine"], 42);
+    }
+
+    #[test]
+    fn should_omit_lines_for_osb_files() {
+        let sarif: Value =
+            serde_json::from_str(&to_sarif("test.osb", &notices()).unwrap()).unwrap();
+
+        let location = &sarif["runs"][0]["results"][0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["region"], Value::Null);
+    }
+}
@@ -18,9 +18,11 @@
  * 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
  */
 use bundles::BundleVersionSpec;
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use clap_complete::Shell;
 
+use crate::checks::compatibility::OnkostarVersion;
+
 #[derive(Parser)]
 #[command(author, version, about)]
 #[command(arg_required_else_help(true))]
@@ -30,6 +32,30 @@ pub struct Cli {
 
     #[arg(short = 'v', global = true, help = "Zeige umfangreichere Ausgaben")]
     pub verbose: bool,
+
+    #[arg(
+        long = "lossless",
+        global = true,
+        help = "Behalte unbekannte Elemente bei, statt die Verarbeitung abzubrechen (Optional)"
+    )]
+    pub lossless: bool,
+
+    #[arg(
+        long = "format",
+        global = true,
+        value_enum,
+        default_value_t = OutputFormat::Text,
+        help = "Ausgabeformat für list, tree, diff, check und modify --dry-run"
+    )]
+    pub format: OutputFormat,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    #[value(help = "Formatierte Textausgabe")]
+    Text,
+    #[value(help = "Maschinenlesbare JSON-Ausgabe mit versioniertem Schema")]
+    Json,
 }
 
 #[derive(Subcommand)]
@@ -66,6 +92,11 @@ pub enum SubCommand {
         sorted: bool,
         #[arg(long = "filter", help = "Filtere Ausgabe nach Name (Optional)")]
         filter: Option<String>,
+        #[arg(
+            long = "library",
+            help = "Verzeichnis mit OSC-Dateien zur Auflösung nicht enthaltener Abhängigkeiten (Optional)"
+        )]
+        library: Option<String>,
     },
     #[command(about = "Modifiziert die angegebene Datei anhand der Profildatei")]
     Modify {
@@ -95,6 +126,94 @@ pub enum SubCommand {
             help = "Erweiterte Problembehandlung und Reparatur der OSC-Datei"
         )]
         fix: bool,
+        #[arg(
+            long = "strict-profile",
+            help = "Abbruch, wenn Regeln der Profildatei keinen passenden Eintrag haben (Optional)"
+        )]
+        strict_profile: bool,
+        #[arg(
+            long = "dry-run",
+            help = "Keine Ausgabedatei erzeugen, sondern die Änderungen je Formular auflisten (Optional)"
+        )]
+        dry_run: bool,
+    },
+    #[command(
+        about = "Extrahiert Formulare mit allen benötigten Katalogen und Formularen in eine neue OSC-Datei"
+    )]
+    Extract {
+        inputfile: String,
+        #[arg(help = "Namen der zu extrahierenden Formulare", required = true)]
+        forms: Vec<String>,
+        #[arg(long = "output", help = "Ausgabedatei (optional)")]
+        outputfile: Option<String>,
+        #[arg(long = "compact", help = "Kompakte Ausgabe, ohne Einrücken (Optional)")]
+        compact: bool,
+    },
+    #[command(
+        about = "Führt mehrere OSC-Dateien anhand der Revision der enthaltenen Inhalte zusammen"
+    )]
+    Merge {
+        #[arg(help = "Zusammenzuführende Dateien", required = true, num_args = 2..)]
+        inputfiles: Vec<String>,
+        #[arg(
+            long = "on-conflict",
+            value_enum,
+            default_value_t = ConflictPolicyArg::Fail,
+            help = "Verhalten bei gleicher Revision mit unterschiedlichem Inhalt"
+        )]
+        on_conflict: ConflictPolicyArg,
+        #[arg(long = "output", help = "Ausgabedatei (optional)")]
+        outputfile: Option<String>,
+        #[arg(long = "compact", help = "Kompakte Ausgabe, ohne Einrücken (Optional)")]
+        compact: bool,
+    },
+    #[command(
+        about = "Teilt eine OSC-Datei in ein Verzeichnis mit einer Datei je Katalog und Formular auf"
+    )]
+    Split {
+        inputfile: String,
+        #[arg(long = "output", help = "Ausgabeverzeichnis", required = true)]
+        outputdir: String,
+    },
+    #[command(about = "Setzt eine mit 'split' aufgeteilte OSC-Datei wieder zusammen")]
+    Join {
+        #[arg(help = "Mit 'split' erstelltes Verzeichnis")]
+        inputdir: String,
+        #[arg(long = "output", help = "Ausgabedatei (optional)")]
+        outputfile: Option<String>,
+        #[arg(long = "compact", help = "Kompakte Ausgabe, ohne Einrücken (Optional)")]
+        compact: bool,
+    },
+    #[command(about = "Gibt Kataloge und Formulare mit Abhängigkeiten als Graph aus")]
+    Graph {
+        inputfile: String,
+        #[arg(
+            long = "graph-format",
+            value_enum,
+            default_value_t = GraphFormat::Dot,
+            help = "Format des Graphen"
+        )]
+        graph_format: GraphFormat,
+        #[arg(
+            long = "root",
+            help = "Beschränke den Graphen auf dieses Formular und dessen Abhängigkeiten (Optional)"
+        )]
+        root: Option<String>,
+        #[arg(long = "output", help = "Ausgabedatei (optional)")]
+        outputfile: Option<String>,
+    },
+    #[command(about = "Erstellt eine Dokumentation aller Formulare und Unterformulare")]
+    Doc {
+        inputfile: String,
+        #[arg(
+            long = "doc-format",
+            value_enum,
+            default_value_t = DocFormat::Markdown,
+            help = "Format der Dokumentation"
+        )]
+        doc_format: DocFormat,
+        #[arg(long = "output", help = "Ausgabedatei (optional)")]
+        outputfile: Option<String>,
     },
     #[command(about = "Vergleiche zwei Dateien anhand der Revision der enthaltenen Inhalte")]
     Diff {
@@ -122,9 +241,43 @@ pub enum SubCommand {
             conflicts_with = "check-file"
         )]
         list: bool,
+        #[arg(
+            long = "fail-on",
+            value_enum,
+            help = "Beende mit Fehler, wenn Hinweise mit diesem oder höherem Schweregrad gefunden wurden (Optional)",
+            requires = "check-file"
+        )]
+        fail_on: Option<SeverityArg>,
+        #[arg(
+            long = "sarif",
+            help = "Schreibe Ergebnis zusätzlich als SARIF-Datei (Optional)",
+            requires = "check-file"
+        )]
+        sariffile: Option<String>,
+        #[arg(
+            long = "config",
+            help = "Konfigurationsdatei der Checks (Optional - Standard: '.osc-variant-checks.yml', sofern vorhanden)"
+        )]
+        configfile: Option<String>,
+        #[arg(
+            long = "library",
+            help = "Verzeichnis mit OSC-Dateien zur Auflösung nicht enthaltener Abhängigkeiten (Optional)",
+            requires = "check-file"
+        )]
+        library: Option<String>,
+        #[arg(
+            long = "target-version",
+            help = "Prüfe Kompatibilität mit dieser Onkostar-Version, z.B. '2.13.3' (Optional)"
+        )]
+        target_version: Option<OnkostarVersion>,
     },
     #[command(about = "Exportiere CSV-Datei mit Ausfüllhinweisen")]
     ExportNoticeCsv { inputfile: String },
+    #[command(
+        subcommand,
+        about = "Befehle zum Bearbeiten enthaltener Skripte als eigenständige Dateien"
+    )]
+    Scripts(ScriptsSubCommand),
     #[command(subcommand, about = "Befehle zur Nutzung von Bundles")]
     Bundle(BundleSubCommand),
     #[cfg(feature = "unzip-osb")]
@@ -142,6 +295,62 @@ pub enum SubCommand {
     },
 }
 
+#[derive(Clone, Copy, ValueEnum)]
+pub enum DocFormat {
+    #[value(help = "Markdown-Dokument")]
+    Markdown,
+    #[value(help = "Eigenständiges HTML-Dokument")]
+    Html,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum GraphFormat {
+    #[value(help = "Graphviz DOT")]
+    Dot,
+    #[value(help = "Mermaid-Flussdiagramm")]
+    Mermaid,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ConflictPolicyArg {
+    #[value(help = "Verwende den Inhalt der zuerst angegebenen Datei")]
+    PreferLeft,
+    #[value(help = "Verwende den Inhalt der zuletzt angegebenen Datei")]
+    PreferRight,
+    #[value(help = "Breche die Zusammenführung ab")]
+    Fail,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum SeverityArg {
+    #[value(help = "Nur Fehler")]
+    Error,
+    #[value(help = "Fehler und Warnungen")]
+    Warning,
+    #[value(help = "Fehler, Warnungen und Informationen")]
+    Info,
+}
+
+#[derive(Subcommand)]
+pub enum ScriptsSubCommand {
+    #[command(about = "Schreibe alle Skripte als einzelne Dateien in ein Verzeichnis")]
+    Extract {
+        inputfile: String,
+        #[arg(long = "output", help = "Ausgabeverzeichnis", required = true)]
+        outputdir: String,
+    },
+    #[command(about = "Übernimm bearbeitete Skripte aus einem Verzeichnis in die OSC-Datei")]
+    Inject {
+        inputfile: String,
+        #[arg(help = "Mit 'scripts extract' erstelltes Verzeichnis")]
+        scriptsdir: String,
+        #[arg(long = "output", help = "Ausgabedatei (optional)")]
+        outputfile: Option<String>,
+        #[arg(long = "compact", help = "Kompakte Ausgabe, ohne Einrücken (Optional)")]
+        compact: bool,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum BundleSubCommand {
     #[command(about = "Suche nach einem Bundle")]
@@ -18,16 +18,30 @@
  * 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
  */
 
-use crate::checks::{CheckNotice, check_file, print};
-use crate::cli::{BundleSubCommand, Cli, SubCommand};
-use crate::console::{PrintableDiff, PrintableList, PrintableTree};
+use crate::checks::compatibility::OnkostarVersion;
+use crate::checks::config::{CheckConfig, DEFAULT_CONFIG_FILE};
+use crate::checks::sarif::to_sarif;
+use crate::checks::{CheckNotice, Fixable, Severity, check_file, print};
+use crate::cli::{
+    BundleSubCommand, Cli, ConflictPolicyArg, DocFormat, GraphFormat, OutputFormat,
+    ScriptsSubCommand, SeverityArg, SubCommand,
+};
+use crate::console::{DisplayableRequirement, PrintableDiff, PrintableList, PrintableTree};
+use crate::doc;
+use crate::dry_run::{ChangeReport, Snapshot};
 use crate::file_io::{FileError, FileReader, InputFile};
+use crate::graph::Graph;
+use crate::json;
+use crate::library::Library;
 use crate::notices::{Notice, WithNotice};
+use crate::scripts;
+use crate::split::{join, split};
 use bundles::{BundleVersionSpec, bundle_info, export_bundle_versions, search_bundle_versions};
 use clap::CommandFactory;
 use clap_complete::{Shell, generate};
 use console::style;
 use encoding_rs::WINDOWS_1252;
+use model::osc::merge::ConflictPolicy;
 use model::osc::onkostar_editor::OnkostarEditor;
 use model::profile::Profile;
 use quick_xml::se::Serializer;
@@ -35,6 +49,7 @@ use serde::Serialize;
 use sha2::{Digest, Sha256};
 use std::cmp::Ordering;
 use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
 use std::fs;
 use std::fs::OpenOptions;
 use std::io::Write;
@@ -59,19 +74,51 @@ macro_rules! update_bundle_repo_or_exit {
     };
 }
 
-pub fn handle(command: SubCommand, verbose: bool) -> Result<(), Box<dyn Error>> {
+/// Error of a command not related to reading or writing files
+pub struct CommandError(String);
+
+impl Error for CommandError {}
+
+impl Debug for CommandError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Display for CommandError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+pub fn handle(
+    command: SubCommand,
+    verbose: bool,
+    lossless: bool,
+    format: OutputFormat,
+) -> Result<(), Box<dyn Error>> {
     match command {
         SubCommand::Completion { shell } => handle_completion(shell),
         SubCommand::List {
             inputfile,
             sorted,
             filter,
-        } => handle_list(inputfile, sorted, filter, verbose)?,
+        } => handle_list(inputfile, sorted, filter, verbose, lossless, format)?,
         SubCommand::Tree {
             inputfile,
             sorted,
             filter,
-        } => handle_tree(inputfile, sorted, filter, verbose)?,
+            library,
+        } => handle_tree(
+            inputfile,
+            sorted,
+            filter,
+            library.as_deref(),
+            verbose,
+            lossless,
+            format,
+        )?,
         SubCommand::Modify {
             inputfile,
             profile,
@@ -81,50 +128,84 @@ pub fn handle(command: SubCommand, verbose: bool) -> Result<(), Box<dyn Error>>
             sorted,
             strip,
             fix,
+            strict_profile,
+            dry_run,
         } => handle_modify(
-            inputfile, profile, noticefile, outputfile, compact, sorted, strip, fix,
+            inputfile,
+            profile,
+            noticefile,
+            outputfile,
+            compact,
+            sorted,
+            strip,
+            fix,
+            strict_profile,
+            dry_run.then_some(format),
+            lossless,
         )?,
+        SubCommand::Extract {
+            inputfile,
+            forms,
+            outputfile,
+            compact,
+        } => handle_extract(&inputfile, &forms, outputfile, compact, lossless)?,
+        SubCommand::Merge {
+            inputfiles,
+            on_conflict,
+            outputfile,
+            compact,
+        } => handle_merge(&inputfiles, on_conflict, outputfile, compact, lossless)?,
+        SubCommand::Split {
+            inputfile,
+            outputdir,
+        } => handle_split(&inputfile, &outputdir, lossless)?,
+        SubCommand::Join {
+            inputdir,
+            outputfile,
+            compact,
+        } => handle_join(&inputdir, outputfile, compact)?,
+        SubCommand::Graph {
+            inputfile,
+            graph_format,
+            root,
+            outputfile,
+        } => handle_graph(&inputfile, graph_format, root, outputfile, lossless)?,
+        SubCommand::Doc {
+            inputfile,
+            doc_format,
+            outputfile,
+        } => handle_doc(&inputfile, doc_format, outputfile, lossless)?,
         SubCommand::Diff {
             inputfile_a,
             inputfile_b,
             strict,
-        } => handle_diff(&inputfile_a, &inputfile_b, strict)?,
+        } => handle_diff(&inputfile_a, &inputfile_b, strict, lossless, format)?,
         SubCommand::Sha256Sum { inputfile } => handle_sha256sum(inputfile),
         SubCommand::Check {
             file,
             list,
             password,
-        } => handle_check(file, list, password),
-        SubCommand::ExportNoticeCsv { inputfile } => handle_export_notice_csv(&inputfile)?,
-        SubCommand::Bundle(command) => match command {
-            #[cfg(feature = "bundle-edit")]
-            BundleSubCommand::Create {
-                bundle_name,
-                description,
-                license,
-                repository,
-            } => handle_create_bundle(bundle_name, description, license, repository)?,
-            #[cfg(feature = "bundle-edit")]
-            BundleSubCommand::AddVersion {
-                bundle_name,
-                file,
-                tag,
-                message,
-                license,
-            } => handle_add_bundle_version(bundle_name, file, tag, message, license)?,
-            BundleSubCommand::List { spec } => handle_list_bundle_version(spec, verbose)?,
-            BundleSubCommand::Search { bundle_name, limit } => {
-                handle_search_bundle(bundle_name, limit)?;
-            }
-            BundleSubCommand::Info { spec } => {
-                handle_bundle_info(spec, verbose)?;
-            }
-            BundleSubCommand::Export { spec, compact } => {
-                handle_export_bundle_version(spec, compact)?;
-            }
-            #[cfg(feature = "bundle-edit")]
-            BundleSubCommand::Cleanup => handle_cleanup_bundle_objects()?,
-        },
+            fail_on,
+            sariffile,
+            configfile,
+            library,
+            target_version,
+        } => handle_check(
+            file,
+            list,
+            password,
+            fail_on,
+            sariffile,
+            configfile,
+            library.as_deref(),
+            target_version,
+            format,
+        )?,
+        SubCommand::ExportNoticeCsv { inputfile } => {
+            handle_export_notice_csv(&inputfile, lossless)?;
+        }
+        SubCommand::Scripts(command) => handle_scripts(command, lossless)?,
+        SubCommand::Bundle(command) => handle_bundle(command, verbose)?,
         #[cfg(feature = "unzip-osb")]
         SubCommand::UnzipOsb {
             file,
@@ -149,6 +230,92 @@ fn write_outputfile(filename: String, content: &String) -> Result<(), FileError>
     Ok(())
 }
 
+/// Serializes OSC content including kept unknown elements
+fn serialize_osc(data: &OnkostarEditor, compact: bool) -> Result<String, FileError> {
+    let mut buf = String::new();
+
+    let mut serializer = Serializer::new(&mut buf);
+    if !compact {
+        serializer.indent(' ', 2);
+    }
+
+    data.serialize(serializer)
+        .map_err(|_| FileError::Writing("Cannot serialize result".to_string(), String::new()))?;
+
+    Ok("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"
+        .to_string()
+        .add(data.restore_unknown_elements(&buf).as_str()))
+}
+
+fn handle_scripts(command: ScriptsSubCommand, lossless: bool) -> Result<(), Box<dyn Error>> {
+    match command {
+        ScriptsSubCommand::Extract {
+            inputfile,
+            outputdir,
+        } => {
+            let data = FileReader::<OnkostarEditor>::read(&inputfile, lossless)?;
+            let count = scripts::extract(&data, &outputdir)?;
+            eprintln!("{count} Skripte nach '{outputdir}' geschrieben");
+        }
+        ScriptsSubCommand::Inject {
+            inputfile,
+            scriptsdir,
+            outputfile,
+            compact,
+        } => {
+            let mut data = FileReader::<OnkostarEditor>::read(&inputfile, lossless)?;
+            for change in scripts::inject(&mut data, &scriptsdir)? {
+                eprintln!("{} {}", style("Übernommen:").green(), change);
+            }
+
+            let output = &serialize_osc(&data, compact)?;
+
+            match outputfile {
+                Some(filename) => write_outputfile(filename, output)?,
+                None => {
+                    println!("{output}");
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_bundle(command: BundleSubCommand, verbose: bool) -> Result<(), Box<dyn Error>> {
+    match command {
+        #[cfg(feature = "bundle-edit")]
+        BundleSubCommand::Create {
+            bundle_name,
+            description,
+            license,
+            repository,
+        } => handle_create_bundle(bundle_name, description, license, repository)?,
+        #[cfg(feature = "bundle-edit")]
+        BundleSubCommand::AddVersion {
+            bundle_name,
+            file,
+            tag,
+            message,
+            license,
+        } => handle_add_bundle_version(bundle_name, file, tag, message, license)?,
+        BundleSubCommand::List { spec } => handle_list_bundle_version(&spec, verbose)?,
+        BundleSubCommand::Search { bundle_name, limit } => {
+            handle_search_bundle(&bundle_name, limit)?;
+        }
+        BundleSubCommand::Info { spec } => {
+            handle_bundle_info(&spec, verbose)?;
+        }
+        BundleSubCommand::Export { spec, compact } => {
+            handle_export_bundle_version(&spec, compact)?;
+        }
+        #[cfg(feature = "bundle-edit")]
+        BundleSubCommand::Cleanup => handle_cleanup_bundle_objects()?,
+    }
+
+    Ok(())
+}
+
 fn handle_completion(shell: Shell) {
     let command = &mut Cli::command();
     generate(
@@ -164,19 +331,53 @@ fn handle_list(
     sorted: bool,
     filter: Option<String>,
     verbose: bool,
+    lossless: bool,
+    format: OutputFormat,
 ) -> Result<(), Box<dyn Error>> {
     match InputFile::read(inputfile, None)? {
         osc @ InputFile::Osc { .. } => {
-            let mut content: OnkostarEditor = osc.try_into()?;
+            let mut content = osc.into_onkostar_editor(lossless)?;
             if sorted {
                 content.sorted();
             }
+            if format == OutputFormat::Json {
+                if let Some(name) = filter {
+                    content.filter_by_name_contains(&name);
+                }
+                println!("{}", json::to_json(json::List::from(&content))?);
+                return Ok(());
+            }
             if let Some(name) = filter {
                 OnkostarEditor::print_list_filtered(&mut content, name.as_str(), verbose);
                 return Ok(());
             }
             content.print_list(verbose);
         }
+        InputFile::Osb { content, .. } if format == OutputFormat::Json => {
+            let mut files = vec![];
+            for file in content {
+                if let InputFile::Osc { .. } = file {
+                    let filename = file.filename();
+                    let mut content = file.into_onkostar_editor(lossless)?;
+                    if sorted {
+                        content.sorted();
+                    }
+                    if let Some(name) = &filter {
+                        content.filter_by_name_contains(name);
+                    }
+                    files.push((filename, content));
+                }
+            }
+            println!(
+                "{}",
+                json::to_json(json::PackageList::new(
+                    files
+                        .iter()
+                        .map(|(filename, content)| (filename.clone(), content))
+                        .collect()
+                ))?
+            );
+        }
         InputFile::Osb { content, .. } => {
             for file in content {
                 match file {
@@ -187,7 +388,7 @@ fn handle_list(
                             style(file.filename()).bold()
                         );
 
-                        let mut content: OnkostarEditor = match file.try_into() {
+                        let mut content = match file.into_onkostar_editor(lossless) {
                             Ok(oe) => oe,
                             Err(err) => {
                                 println!("{err}");
@@ -234,19 +435,31 @@ fn handle_tree(
     inputfile: String,
     sorted: bool,
     filter: Option<String>,
+    library: Option<&str>,
     verbose: bool,
+    lossless: bool,
+    format: OutputFormat,
 ) -> Result<(), Box<dyn Error>> {
     match InputFile::read(inputfile, None)? {
         osc @ InputFile::Osc { .. } => {
-            let mut content: OnkostarEditor = osc.try_into()?;
+            let mut content = osc.into_onkostar_editor(lossless)?;
+            let library = library.map(FileReader::<Library>::read).transpose()?;
+            let library = library.as_ref();
             if sorted {
                 content.sorted();
             }
+            if format == OutputFormat::Json {
+                if let Some(name) = filter {
+                    content.filter_by_name_contains(&name);
+                }
+                println!("{}", json::to_json(json::Tree::new(&content, library))?);
+                return Ok(());
+            }
             if let Some(name) = filter {
-                OnkostarEditor::print_tree_filtered(&mut content, name.as_str(), verbose);
+                OnkostarEditor::print_tree_filtered(&mut content, name.as_str(), library, verbose);
                 return Ok(());
             }
-            OnkostarEditor::print_tree(&content, verbose);
+            OnkostarEditor::print_tree(&content, library, verbose);
         }
         InputFile::Osb { filename, .. } => {
             return Err(Box::new(FileError::Reading(
@@ -266,6 +479,8 @@ fn handle_tree(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::fn_params_excessive_bools)]
 fn handle_modify(
     inputfile: String,
     profile: Option<String>,
@@ -275,25 +490,50 @@ fn handle_modify(
     sorted: bool,
     strip: bool,
     fix: bool,
+    strict_profile: bool,
+    dry_run: Option<OutputFormat>,
+    lossless: bool,
 ) -> Result<(), Box<dyn Error>> {
-    let mut data: OnkostarEditor = InputFile::read(inputfile, None)?.try_into()?;
+    let mut data = InputFile::read(inputfile, None)?.into_onkostar_editor(lossless)?;
+    let snapshot = dry_run.map(|_| Snapshot::new(&data));
+
+    for unknown_element in &data.unknown_elements {
+        eprintln!(
+            "{} '{}' in {} (Zeile {}) wird unverändert übernommen",
+            style("Unbekanntes Element").yellow(),
+            unknown_element.name,
+            unknown_element.location,
+            unknown_element.line
+        );
+    }
 
     if let Some(profile) = profile {
         let profile = if profile.contains('.') {
             FileReader::<Profile>::read(&profile)?
         } else {
-            Profile::embedded_profile(&profile)?
+            FileReader::<Profile>::read_embedded(&profile)?
         };
 
-        data.apply_profile(&profile);
+        let unmatched = data.apply_profile(&profile);
+        for rule in &unmatched {
+            eprintln!("{} {}", style("Nicht angewendet:").yellow(), rule);
+        }
+        if strict_profile && !unmatched.is_empty() {
+            return Err(Box::new(CommandError(format!(
+                "Profil nicht vollständig anwendbar: {} Regel(n) ohne passenden Eintrag",
+                unmatched.len()
+            ))));
+        }
     }
 
     if fix {
-        // No operation as of now
+        for fixed in data.fix() {
+            eprintln!("{} {}", style("Behoben:").green(), fixed);
+        }
     }
 
     if sorted {
-        data.sorted();
+        print_requirement_cycles(&data.sorted());
     }
 
     if strip {
@@ -336,19 +576,95 @@ fn handle_modify(
             .for_each(|form| form.apply_notices(notices.clone()));
     }
 
-    let mut buf = String::new();
+    if let (Some(format), Some(snapshot)) = (dry_run, snapshot) {
+        let report = ChangeReport::new(snapshot, &data);
+        match format {
+            OutputFormat::Json => println!("{}", json::to_json(report)?),
+            OutputFormat::Text => report.print(),
+        }
+        return Ok(());
+    }
 
-    let mut serializer = Serializer::new(&mut buf);
-    if !compact {
-        serializer.indent(' ', 2);
+    let output = &serialize_osc(&data, compact)?;
+
+    match outputfile {
+        Some(filename) => write_outputfile(filename, output)?,
+        None => {
+            println!("{output}");
+        }
     }
 
-    data.serialize(serializer)
-        .map_err(|_| FileError::Writing("Cannot serialize result".to_string(), String::new()))?;
+    Ok(())
+}
 
-    let output = &"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"
-        .to_string()
-        .add(buf.as_str());
+fn handle_graph(
+    inputfile: &str,
+    graph_format: GraphFormat,
+    root: Option<String>,
+    outputfile: Option<String>,
+    lossless: bool,
+) -> Result<(), Box<dyn Error>> {
+    let data = FileReader::<OnkostarEditor>::read(inputfile, lossless)?;
+
+    let mut graph = Graph::new(&data);
+    if let Some(root) = root {
+        graph.restrict_to(&root).map_err(CommandError)?;
+    }
+
+    let output = &match graph_format {
+        GraphFormat::Dot => graph.to_dot(),
+        GraphFormat::Mermaid => graph.to_mermaid(),
+    };
+
+    match outputfile {
+        Some(filename) => write_outputfile(filename, output)?,
+        None => {
+            print!("{output}");
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_doc(
+    inputfile: &str,
+    doc_format: DocFormat,
+    outputfile: Option<String>,
+    lossless: bool,
+) -> Result<(), Box<dyn Error>> {
+    let data = FileReader::<OnkostarEditor>::read(inputfile, lossless)?;
+
+    let output = &match doc_format {
+        DocFormat::Markdown => doc::to_markdown(&data),
+        DocFormat::Html => doc::to_html(&data),
+    };
+
+    match outputfile {
+        Some(filename) => write_outputfile(filename, output)?,
+        None => {
+            print!("{output}");
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_split(inputfile: &str, outputdir: &str, lossless: bool) -> Result<(), Box<dyn Error>> {
+    let mut data = FileReader::<OnkostarEditor>::read(inputfile, lossless)?;
+    split(&mut data, outputdir)?;
+
+    Ok(())
+}
+
+fn handle_join(
+    inputdir: &str,
+    outputfile: Option<String>,
+    compact: bool,
+) -> Result<(), Box<dyn Error>> {
+    let (data, cycles) = join(inputdir)?;
+    print_requirement_cycles(&cycles);
+
+    let output = &serialize_osc(&data, compact)?;
 
     match outputfile {
         Some(filename) => write_outputfile(filename, output)?,
@@ -360,15 +676,134 @@ fn handle_modify(
     Ok(())
 }
 
-fn handle_diff(inputfile_a: &str, inputfile_b: &str, strict: bool) -> Result<(), Box<dyn Error>> {
+fn handle_extract(
+    inputfile: &str,
+    forms: &[String],
+    outputfile: Option<String>,
+    compact: bool,
+    lossless: bool,
+) -> Result<(), Box<dyn Error>> {
+    let mut data = FileReader::<OnkostarEditor>::read(inputfile, lossless)?;
+
+    for requirement in data.extract(forms).map_err(CommandError)? {
+        eprintln!("{} {}", style("Warnung:").yellow(), requirement.to_string());
+    }
+
+    print_requirement_cycles(&data.sort_forms_by_requirements());
+
+    let output = &serialize_osc(&data, compact)?;
+
+    match outputfile {
+        Some(filename) => write_outputfile(filename, output)?,
+        None => {
+            println!("{output}");
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_merge(
+    inputfiles: &[String],
+    on_conflict: ConflictPolicyArg,
+    outputfile: Option<String>,
+    compact: bool,
+    lossless: bool,
+) -> Result<(), Box<dyn Error>> {
+    let policy = match on_conflict {
+        ConflictPolicyArg::PreferLeft => ConflictPolicy::PreferLeft,
+        ConflictPolicyArg::PreferRight => ConflictPolicy::PreferRight,
+        ConflictPolicyArg::Fail => ConflictPolicy::Fail,
+    };
+
+    let Some((first, others)) = inputfiles.split_first() else {
+        return Ok(());
+    };
+
+    let mut data = FileReader::<OnkostarEditor>::read(first, lossless)?;
+
+    for inputfile in others {
+        let other = FileReader::<OnkostarEditor>::read(inputfile, lossless)?;
+        match data.merge(other, policy) {
+            Ok(conflicts) => {
+                for conflict in conflicts {
+                    eprintln!(
+                        "{} {} - {}",
+                        style("Konflikt:").yellow(),
+                        conflict,
+                        if policy == ConflictPolicy::PreferRight {
+                            format!("verwende Inhalt aus '{inputfile}'")
+                        } else {
+                            "behalte bisherigen Inhalt".to_string()
+                        }
+                    );
+                }
+            }
+            Err(conflicts) => {
+                for conflict in &conflicts {
+                    eprintln!("{} {}", style("Konflikt:").red(), conflict);
+                }
+                return Err(Box::new(CommandError(format!(
+                    "Zusammenführung mit '{inputfile}' wegen {} Konflikt(en) abgebrochen",
+                    conflicts.len()
+                ))));
+            }
+        }
+    }
+
+    print_requirement_cycles(&data.sort_forms_by_requirements());
+
+    let output = &serialize_osc(&data, compact)?;
+
+    match outputfile {
+        Some(filename) => write_outputfile(filename, output)?,
+        None => {
+            println!("{output}");
+        }
+    }
+
+    Ok(())
+}
+
+fn print_requirement_cycles(cycles: &[Vec<String>]) {
+    for cycle in cycles {
+        eprintln!(
+            "{} {} - Import in mehreren Durchgängen erforderlich",
+            style("Gegenseitige Abhängigkeit der Formulare").yellow(),
+            cycle
+                .iter()
+                .map(|name| format!("'{name}'"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+}
+
+fn handle_diff(
+    inputfile_a: &str,
+    inputfile_b: &str,
+    strict: bool,
+    lossless: bool,
+    format: OutputFormat,
+) -> Result<(), Box<dyn Error>> {
+    if format == OutputFormat::Json {
+        let data_a = &mut FileReader::<OnkostarEditor>::read(inputfile_a, lossless)?;
+        let data_b = &mut FileReader::<OnkostarEditor>::read(inputfile_b, lossless)?;
+        println!(
+            "{}",
+            json::to_json(json::Diff::new(data_a, data_b, strict))?
+        );
+        return Ok(());
+    }
+
     println!(
         "Vergleiche Datei A ({}) mit Datei B ({})",
         style(&inputfile_a).yellow(),
         style(&inputfile_b).yellow()
     );
 
-    let data_a = &mut FileReader::<OnkostarEditor>::read(inputfile_a)?;
-    let data_b = &mut FileReader::<OnkostarEditor>::read(inputfile_b)?;
+    let data_a = &mut FileReader::<OnkostarEditor>::read(inputfile_a, lossless)?;
+    let data_b = &mut FileReader::<OnkostarEditor>::read(inputfile_b, lossless)?;
 
     data_a.print_diff(data_b, strict);
 
@@ -397,12 +832,54 @@ fn handle_sha256sum(inputfile: String) {
     }
 }
 
-fn handle_check(file: Option<String>, list: bool, password: Option<String>) {
+#[allow(clippy::too_many_arguments)]
+fn handle_check(
+    file: Option<String>,
+    list: bool,
+    password: Option<String>,
+    fail_on: Option<SeverityArg>,
+    sariffile: Option<String>,
+    configfile: Option<String>,
+    library: Option<&str>,
+    target_version: Option<OnkostarVersion>,
+    format: OutputFormat,
+) -> Result<(), Box<dyn Error>> {
+    let configfile = configfile.or_else(|| {
+        Path::new(DEFAULT_CONFIG_FILE)
+            .is_file()
+            .then(|| DEFAULT_CONFIG_FILE.to_string())
+    });
+    let mut config = match configfile {
+        Some(ref configfile) => FileReader::<CheckConfig>::read(configfile)?,
+        None => CheckConfig::default(),
+    };
+    if target_version.is_some() {
+        config.target_version = target_version;
+    }
+
     if list {
-        print();
-    } else {
-        match check_file(Path::new(file.unwrap_or_default().as_str()), password) {
-            Ok(notices) => {
+        if format == OutputFormat::Json {
+            println!(
+                "{}",
+                json::to_json(json::ProblemList::new(&config, configfile.as_deref()))?
+            );
+        } else {
+            print(&config, configfile.as_deref());
+        }
+        return Ok(());
+    }
+
+    let library = library.map(FileReader::<Library>::read).transpose()?;
+
+    let file = file.unwrap_or_default();
+    let notices = match check_file(
+        Path::new(file.as_str()),
+        password,
+        &config,
+        library.as_ref(),
+    ) {
+        Ok(notices) => {
+            if format == OutputFormat::Text {
                 println!(
                     "Es wurden {} Probleme gefunden\n",
                     notices
@@ -413,19 +890,49 @@ fn handle_check(file: Option<String>, list: bool, password: Option<String>) {
                         ))
                         .count()
                 );
-                for notice in notices {
-                    println!("{notice}");
-                }
             }
-            Err(err) => {
-                println!("{err}");
+            notices
+        }
+        Err(err) => vec![err],
+    };
+
+    match format {
+        OutputFormat::Text => {
+            for notice in &notices {
+                println!("{notice}");
             }
         }
+        OutputFormat::Json => {
+            println!("{}", json::to_json(json::Check::new(&file, &notices))?);
+        }
+    }
+
+    if let Some(sariffile) = sariffile {
+        write_outputfile(sariffile, &to_sarif(&file, &notices)?)?;
+    }
+
+    if let Some(fail_on) = fail_on {
+        let severity = match fail_on {
+            SeverityArg::Error => Severity::Error,
+            SeverityArg::Warning => Severity::Warning,
+            SeverityArg::Info => Severity::Info,
+        };
+        let count = notices
+            .iter()
+            .filter(|notice| notice.is_at_least(severity))
+            .count();
+        if count > 0 {
+            return Err(Box::new(CommandError(format!(
+                "Prüfung fehlgeschlagen: {count} Hinweis(e) mit dem angegebenen oder höherem Schweregrad gefunden"
+            ))));
+        }
     }
+
+    Ok(())
 }
 
-fn handle_export_notice_csv(inputfile: &str) -> Result<(), Box<dyn Error>> {
-    let data = &mut FileReader::<OnkostarEditor>::read(inputfile)?;
+fn handle_export_notice_csv(inputfile: &str, lossless: bool) -> Result<(), Box<dyn Error>> {
+    let data = &mut FileReader::<OnkostarEditor>::read(inputfile, lossless)?;
 
     let mut notices = data
         .editor
@@ -490,21 +997,21 @@ fn handle_add_bundle_version(
     license: Option<String>,
 ) -> Result<(), Box<dyn Error>> {
     update_bundle_repo_or_exit!();
-    let data = &mut FileReader::<OnkostarEditor>::read(&file)?;
+    let data = &mut FileReader::<OnkostarEditor>::read(&file, false)?;
     add_bundle_version(&name, data, tag, message, license).map_err(Box::new)?;
     Ok(())
 }
 
-fn handle_search_bundle(name: String, limit: usize) -> Result<(), Box<dyn Error>> {
+fn handle_search_bundle(name: &str, limit: usize) -> Result<(), Box<dyn Error>> {
     update_bundle_repo_or_exit!();
-    let matches = search_bundle_versions(&name).map_err(Box::new)?;
+    let matches = search_bundle_versions(name).map_err(Box::new)?;
     for bundle_info in matches.iter().take(limit) {
         let formatted_name = format!(
             "{} = \"{}\"",
             bundle_info
                 .name
                 .clone()
-                .replace(&name, &style(&name).green().bold().bright().to_string()),
+                .replace(name, &style(name).green().bold().bright().to_string()),
             bundle_info.version
         );
         let description = bundle_info.description.clone().unwrap_or_default();
@@ -520,9 +1027,9 @@ fn handle_search_bundle(name: String, limit: usize) -> Result<(), Box<dyn Error>
     Ok(())
 }
 
-fn handle_bundle_info(spec: BundleVersionSpec, _: bool) -> Result<(), Box<dyn Error>> {
+fn handle_bundle_info(spec: &BundleVersionSpec, _: bool) -> Result<(), Box<dyn Error>> {
     update_bundle_repo_or_exit!();
-    let bundle_info = bundle_info(&spec).map_err(Box::new)?;
+    let bundle_info = bundle_info(spec).map_err(Box::new)?;
 
     println!("{}", style(bundle_info.name).bold().green().bright());
     if let Some(value) = bundle_info.description {
@@ -561,7 +1068,7 @@ fn handle_bundle_info(spec: BundleVersionSpec, _: bool) -> Result<(), Box<dyn Er
 
     println!();
 
-    let data = export_bundle_versions(&spec)?;
+    let data = export_bundle_versions(spec)?;
     let mut buf = String::new();
     let mut serializer = Serializer::new(&mut buf);
     serializer.indent(' ', 2);
@@ -588,38 +1095,23 @@ fn handle_bundle_info(spec: BundleVersionSpec, _: bool) -> Result<(), Box<dyn Er
 }
 
 fn handle_list_bundle_version(
-    spec: BundleVersionSpec,
+    spec: &BundleVersionSpec,
     verbose: bool,
 ) -> Result<(), Box<dyn Error>> {
     update_bundle_repo_or_exit!();
-    let data = export_bundle_versions(&spec)?;
+    let data = export_bundle_versions(spec)?;
     OnkostarEditor::print_list(&data, verbose);
     Ok(())
 }
 
 fn handle_export_bundle_version(
-    spec: BundleVersionSpec,
+    spec: &BundleVersionSpec,
     compact: bool,
 ) -> Result<(), Box<dyn Error>> {
     update_bundle_repo_or_exit!();
-    let data = export_bundle_versions(&spec)?;
-
-    let mut buf = String::new();
-
-    let mut serializer = Serializer::new(&mut buf);
-
-    if !compact {
-        serializer.indent(' ', 2);
-    }
-
-    data.serialize(serializer)
-        .map_err(|_| FileError::Writing("Cannot serialize result".to_string(), String::new()))?;
-
-    let output = &"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"
-        .to_string()
-        .add(buf.as_str());
+    let data = export_bundle_versions(spec)?;
 
-    println!("{output}");
+    println!("{}", serialize_osc(&data, compact)?);
 
     Ok(())
 }
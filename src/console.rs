@@ -17,13 +17,16 @@
  * with this program; if not, write to the Free Software Foundation, Inc.,
  * 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
  */
+use crate::library::{Library, external_requirement};
 use console::style;
 use model::osc::data_catalogue::DataCatalogue;
+use model::osc::diff::{Change, diff};
 use model::osc::form::{DataFormType, Form};
 use model::osc::onkostar_editor::OnkostarEditor;
 use model::osc::property_catalogue::PropertyCatalogue;
 use model::osc::requirements::{Requirement, Requires};
 use model::osc::{Comparable, FolderContained, Named, Revisioned};
+use serde::Serialize;
 use std::any::TypeId;
 use std::cmp::Ordering;
 
@@ -96,14 +99,24 @@ impl PrintableItemList for PropertyCatalogue {
 }
 
 pub trait PrintableRequirement {
-    fn to_requirement_string<'a>(&'a self, all: &'a OnkostarEditor, verbose: bool) -> String;
+    fn to_requirement_string<'a>(
+        &'a self,
+        all: &'a OnkostarEditor,
+        library: Option<&Library>,
+        verbose: bool,
+    ) -> String;
 }
 
 impl<T> PrintableRequirement for Form<T>
 where
     Self: PrintableItemList + Requires,
 {
-    fn to_requirement_string<'a>(&'a self, all: &'a OnkostarEditor, verbose: bool) -> String {
+    fn to_requirement_string<'a>(
+        &'a self,
+        all: &'a OnkostarEditor,
+        library: Option<&Library>,
+        verbose: bool,
+    ) -> String {
         format!(
             "{}\n{}",
             if verbose {
@@ -119,9 +132,9 @@ where
                             .get_required_entries(all)
                             .iter()
                             .map(|inner_entry| match inner_entry {
-                                Requirement::PropertyCatalogue(_) => Some(inner_entry.to_string()),
-                                Requirement::ExternalPropertyCatalogue(_) => {
-                                    Some(inner_entry.to_string())
+                                Requirement::PropertyCatalogue(_)
+                                | Requirement::ExternalPropertyCatalogue(_) => {
+                                    Some(inner_entry.to_resolved_string(library))
                                 }
                                 _ => None,
                             })
@@ -134,19 +147,19 @@ where
                         }
                     }
                     Requirement::ExternalDataCatalogue(_) => {
-                        Some(format!("  + {}\n", entry.to_string()))
+                        Some(format!("  + {}\n", entry.to_resolved_string(library)))
                     }
                     Requirement::DataFormReference(_)
                     | Requirement::ExternalDataFormReference(_)
                     | Requirement::UnterformularReference(_)
                     | Requirement::ExternalUnterformularReference(_) => {
-                        Some(format!("  > {}\n", entry.to_string()))
+                        Some(format!("  > {}\n", entry.to_resolved_string(library)))
                     }
                     Requirement::DataFormSubform(_)
                     | Requirement::ExternalDataFormSubform(_)
                     | Requirement::UnterformularSubform(_)
                     | Requirement::ExternalUnterformularSubform(_) => {
-                        Some(format!("  * {}\n", entry.to_string()))
+                        Some(format!("  * {}\n", entry.to_resolved_string(library)))
                     }
                     _ => None,
                 })
@@ -156,7 +169,12 @@ where
 }
 
 impl PrintableRequirement for DataCatalogue {
-    fn to_requirement_string<'a>(&'a self, all: &'a OnkostarEditor, verbose: bool) -> String {
+    fn to_requirement_string<'a>(
+        &'a self,
+        all: &'a OnkostarEditor,
+        library: Option<&Library>,
+        verbose: bool,
+    ) -> String {
         format!(
             "{}\n{}",
             if verbose {
@@ -169,7 +187,7 @@ impl PrintableRequirement for DataCatalogue {
                 .filter_map(|entry| match entry {
                     Requirement::PropertyCatalogue(_)
                     | Requirement::ExternalPropertyCatalogue(_) => {
-                        Some(format!("  - {}\n", entry.to_string()))
+                        Some(format!("  - {}\n", entry.to_resolved_string(library)))
                     }
                     _ => None,
                 })
@@ -232,13 +250,19 @@ impl PrintableList for OnkostarEditor {
 }
 
 pub trait PrintableTree {
-    fn print_tree(&self, verbose: bool);
-    fn print_tree_filtered(&mut self, name: &str, verbose: bool);
-    fn print_items_tree(&self, title: &str, list: &[impl PrintableRequirement], verbose: bool);
+    fn print_tree(&self, library: Option<&Library>, verbose: bool);
+    fn print_tree_filtered(&mut self, name: &str, library: Option<&Library>, verbose: bool);
+    fn print_items_tree(
+        &self,
+        title: &str,
+        list: &[impl PrintableRequirement],
+        library: Option<&Library>,
+        verbose: bool,
+    );
 }
 
 impl PrintableTree for OnkostarEditor {
-    fn print_tree(&self, verbose: bool) {
+    fn print_tree(&self, library: Option<&Library>, verbose: bool) {
         println!(
             "Die Datei wurde am {} mit {} in Version {} erstellt.\n\nFolgende Inhalte sind gespeichert",
             style(&self.info_xml.datum_xml).yellow(),
@@ -247,12 +271,22 @@ impl PrintableTree for OnkostarEditor {
         );
 
         Self::print_items("Merkmalskataloge", &self.editor.property_catalogue, verbose);
-        self.print_items_tree("Datenkataloge", &self.editor.data_catalogue, verbose);
-        self.print_items_tree("Formulare", &self.editor.data_form, verbose);
-        self.print_items_tree("Unterformulare", &self.editor.unterformular, verbose);
+        self.print_items_tree(
+            "Datenkataloge",
+            &self.editor.data_catalogue,
+            library,
+            verbose,
+        );
+        self.print_items_tree("Formulare", &self.editor.data_form, library, verbose);
+        self.print_items_tree(
+            "Unterformulare",
+            &self.editor.unterformular,
+            library,
+            verbose,
+        );
     }
 
-    fn print_tree_filtered(&mut self, name: &str, verbose: bool) {
+    fn print_tree_filtered(&mut self, name: &str, library: Option<&Library>, verbose: bool) {
         println!(
             "Die Datei wurde am {} mit {} in Version {} erstellt.\n\nFolgende Inhalte für '{}' sind gespeichert",
             style(&self.info_xml.datum_xml).yellow(),
@@ -264,19 +298,35 @@ impl PrintableTree for OnkostarEditor {
         self.filter_by_name_contains(name);
 
         Self::print_items("Merkmalskataloge", &self.editor.property_catalogue, verbose);
-        self.print_items_tree("Datenkataloge", &self.editor.data_catalogue, verbose);
-        self.print_items_tree("Formulare", &self.editor.data_form, verbose);
-        self.print_items_tree("Unterformulare", &self.editor.unterformular, verbose);
+        self.print_items_tree(
+            "Datenkataloge",
+            &self.editor.data_catalogue,
+            library,
+            verbose,
+        );
+        self.print_items_tree("Formulare", &self.editor.data_form, library, verbose);
+        self.print_items_tree(
+            "Unterformulare",
+            &self.editor.unterformular,
+            library,
+            verbose,
+        );
     }
 
-    fn print_items_tree(&self, title: &str, list: &[impl PrintableRequirement], verbose: bool) {
+    fn print_items_tree(
+        &self,
+        title: &str,
+        list: &[impl PrintableRequirement],
+        library: Option<&Library>,
+        verbose: bool,
+    ) {
         print!("\n{} {}", list.len(), style(title).underlined());
         println!(
             " - Inhalte der Systembibliothek sind mit ({}), der Benutzerbibliothek mit (u) markiert",
             style("S").yellow()
         );
         for entry in list {
-            println!("{}", entry.to_requirement_string(self, verbose));
+            println!("{}", entry.to_requirement_string(self, library, verbose));
         }
     }
 }
@@ -285,8 +335,8 @@ pub trait PrintableDiff {
     fn print_diff(&mut self, other: &mut Self, strict: bool);
     fn print_item_diff(
         title: &str,
-        list_a: &[impl Comparable + Named],
-        list_b: &[impl Comparable + Named],
+        list_a: &[impl Comparable + Serialize],
+        list_b: &[impl Comparable + Serialize],
         strict: bool,
     );
 }
@@ -340,8 +390,8 @@ impl PrintableDiff for OnkostarEditor {
 
     fn print_item_diff(
         title: &str,
-        list_a: &[impl Comparable + Named],
-        list_b: &[impl Comparable + Named],
+        list_a: &[impl Comparable + Serialize],
+        list_b: &[impl Comparable + Serialize],
         strict: bool,
     ) {
         println!("\n{}", style(title).underlined());
@@ -377,6 +427,9 @@ impl PrintableDiff for OnkostarEditor {
                                 style(entry_a.get_revision()).blue(),
                                 style(entry_b.get_revision()).green()
                             );
+                            if strict {
+                                print_changes(&diff(entry_a, entry_b));
+                            }
                             has_diff = true;
                         }
                         Ordering::Greater => {
@@ -387,15 +440,28 @@ impl PrintableDiff for OnkostarEditor {
                                 style(entry_a.get_revision()).green(),
                                 style(entry_b.get_revision()).blue()
                             );
+                            if strict {
+                                print_changes(&diff(entry_a, entry_b));
+                            }
                             has_diff = true;
                         }
                         Ordering::Equal => {
                             if strict && entry_a.get_hash() != entry_b.get_hash() {
-                                println!(
-                                    "{}: {} (z.B. GUID oder Reihenfolge von Unterelementen)",
-                                    entry_a.get_name(),
-                                    style("Inhaltlich verschieden").yellow()
-                                );
+                                let changes = diff(entry_a, entry_b);
+                                if changes.is_empty() {
+                                    println!(
+                                        "{}: {} (z.B. Reihenfolge von Unterelementen)",
+                                        entry_a.get_name(),
+                                        style("Inhaltlich verschieden").yellow()
+                                    );
+                                } else {
+                                    println!(
+                                        "{}: {}",
+                                        entry_a.get_name(),
+                                        style("Inhaltlich verschieden").yellow()
+                                    );
+                                    print_changes(&changes);
+                                }
                                 has_diff = true;
                             } else if strict {
                                 println!("{}: {}", entry_a.get_name(), style("Identisch").green());
@@ -412,8 +478,55 @@ impl PrintableDiff for OnkostarEditor {
     }
 }
 
-trait DisplayableRequirement {
+fn print_changes(changes: &[Change]) {
+    let value = |value: &Option<String>| match value {
+        Some(value) => format!("'{value}'"),
+        None => "(nicht vorhanden)".to_string(),
+    };
+
+    for change in changes {
+        match change {
+            Change::Added { element } => {
+                println!(
+                    "  {} {}: Nur in Datei B enthalten",
+                    style("+").green(),
+                    element
+                );
+            }
+            Change::Removed { element } => {
+                println!(
+                    "  {} {}: Nur in Datei A enthalten",
+                    style("-").red(),
+                    element
+                );
+            }
+            Change::Changed {
+                element,
+                property,
+                old,
+                new,
+            } => {
+                println!(
+                    "  {} {}{}: {} -> {}",
+                    style("~").yellow(),
+                    element
+                        .as_ref()
+                        .map(|element| format!("{element} > "))
+                        .unwrap_or_default(),
+                    style(property).bold(),
+                    style(value(old)).blue(),
+                    style(value(new)).green()
+                );
+            }
+        }
+    }
+}
+
+pub trait DisplayableRequirement {
     fn to_string(&self) -> String;
+
+    /// Shows the files of the library providing a requirement not contained in the file
+    fn to_resolved_string(&self, library: Option<&Library>) -> String;
 }
 
 impl DisplayableRequirement for Requirement<'_> {
@@ -443,4 +556,30 @@ impl DisplayableRequirement for Requirement<'_> {
             }
         }
     }
+
+    fn to_resolved_string(&self, library: Option<&Library>) -> String {
+        let (Some(library), Some((content_type, name))) = (library, external_requirement(self))
+        else {
+            return self.to_string();
+        };
+
+        match library.find(content_type, name) {
+            [] => format!(
+                "{content_type} (-) '{name}' - {}",
+                style("in keiner Datei enthalten").red()
+            ),
+            items => format!(
+                "{content_type} (-) '{name}' - enthalten in {}",
+                items
+                    .iter()
+                    .map(|item| format!(
+                        "'{}' in Revision '{}'",
+                        style(&item.file).yellow(),
+                        style(item.revision).yellow()
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
 }
@@ -0,0 +1,585 @@
+/*
+ * This file is part of osc-variant
+ *
+ * Copyright (C) 2026 the original author or authors.
+ *
+ * This program is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation; either version 2 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along
+ * with this program; if not, write to the Free Software Foundation, Inc.,
+ * 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
+ */
+
+//! Documentation of form structures as data dictionary in Markdown or HTML
+
+use std::cmp::Ordering;
+use std::fmt::Write;
+
+use model::osc::data_catalogue::DataCatalogue;
+use model::osc::form::Form;
+use model::osc::merge::ContentType;
+use model::osc::onkostar_editor::OnkostarEditor;
+use model::osc::other::Entry;
+use model::osc::{Named, TypedEntry};
+use quick_xml::escape::escape;
+
+struct FormDoc {
+    name: String,
+    content_type: ContentType,
+    description: Option<String>,
+    sections: Vec<SectionDoc>,
+}
+
+struct SectionDoc {
+    name: Option<String>,
+    description: Option<String>,
+    fields: Vec<FieldDoc>,
+}
+
+struct FieldDoc {
+    name: String,
+    description: String,
+    field_type: String,
+    data_catalogue_entry: Option<String>,
+    mandatory: String,
+    filter: Option<String>,
+    default_value: Option<String>,
+    hinweis: Option<String>,
+    reference: Option<Reference>,
+    property_catalogue: Option<(String, Vec<(String, String)>)>,
+}
+
+struct Reference {
+    name: String,
+    /// Type of the referenced form, if contained
+    content_type: Option<ContentType>,
+}
+
+const HEADERS: [&str; 8] = [
+    "Name",
+    "Beschreibung",
+    "Typ",
+    "Datenkatalogeintrag",
+    "Pflichtfeld",
+    "Filter",
+    "Standardwert",
+    "Hinweis",
+];
+
+/// Renders all forms and subforms as Markdown document
+pub fn to_markdown(data: &OnkostarEditor) -> String {
+    let forms = form_docs(data);
+    let mut result = String::from("# Formulare\n\n");
+
+    for form in &forms {
+        let _ = writeln!(
+            result,
+            "* [{}](#{}) ({})",
+            markdown(&form.name),
+            anchor(form.content_type, &form.name),
+            form.content_type
+        );
+    }
+    result.push('\n');
+
+    for form in &forms {
+        let _ = write!(
+            result,
+            "<a id=\"{}\"></a>\n\n## {}: {}\n\n",
+            anchor(form.content_type, &form.name),
+            form.content_type,
+            markdown(&form.name)
+        );
+        if let Some(description) = &form.description {
+            let _ = write!(result, "{}\n\n", markdown(description));
+        }
+
+        for section in &form.sections {
+            match &section.name {
+                Some(name) => {
+                    let _ = write!(result, "### Abschnitt '{}'\n\n", markdown(name));
+                }
+                None => result.push_str("### Felder\n\n"),
+            }
+            if let Some(description) = &section.description {
+                let _ = write!(result, "{}\n\n", markdown(description));
+            }
+
+            let _ = writeln!(result, "| {} |", HEADERS.join(" | "));
+            let _ = writeln!(result, "|{}", " --- |".repeat(HEADERS.len()));
+            for field in &section.fields {
+                let _ = writeln!(
+                    result,
+                    "| {} | {} | {} | {} | {} | {} | {} | {} |",
+                    markdown_name(field),
+                    markdown(&field.description),
+                    markdown(&field.field_type),
+                    markdown_or_empty(field.data_catalogue_entry.as_ref()),
+                    markdown(&field.mandatory),
+                    field
+                        .filter
+                        .as_ref()
+                        .map(|filter| format!("`{}`", markdown(filter)))
+                        .unwrap_or_default(),
+                    markdown_or_empty(field.default_value.as_ref()),
+                    markdown_or_empty(field.hinweis.as_ref()),
+                );
+            }
+            result.push('\n');
+
+            for field in &section.fields {
+                if let Some((catalogue, codes)) = &field.property_catalogue {
+                    let _ = write!(
+                        result,
+                        "#### Auswahlwerte für '{}' (Merkmalskatalog '{}')\n\n",
+                        markdown(&field.name),
+                        markdown(catalogue)
+                    );
+                    for (code, description) in codes {
+                        let _ =
+                            writeln!(result, "* `{}`: {}", markdown(code), markdown(description));
+                    }
+                    result.push('\n');
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Renders all forms and subforms as self-contained HTML document
+pub fn to_html(data: &OnkostarEditor) -> String {
+    let forms = form_docs(data);
+    let mut result = String::from(concat!(
+        "<!DOCTYPE html>\n<html lang=\"de\">\n<head>\n<meta charset=\"UTF-8\">\n",
+        "<title>Formulare</title>\n<style>\n",
+        "body { font-family: sans-serif; margin: 2em; }\n",
+        "table { border-collapse: collapse; width: 100%; margin-bottom: 1em; }\n",
+        "th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: left; vertical-align: top; }\n",
+        "th { background: #eee; }\n",
+        "code { white-space: pre-wrap; }\n",
+        "</style>\n</head>\n<body>\n<h1>Formulare</h1>\n<ul>\n"
+    ));
+
+    for form in &forms {
+        let _ = writeln!(
+            result,
+            "<li><a href=\"#{}\">{}</a> ({})</li>",
+            anchor(form.content_type, &form.name),
+            escape(&form.name),
+            form.content_type
+        );
+    }
+    result.push_str("</ul>\n");
+
+    for form in &forms {
+        let _ = writeln!(
+            result,
+            "<h2 id=\"{}\">{}: {}</h2>",
+            anchor(form.content_type, &form.name),
+            form.content_type,
+            escape(&form.name)
+        );
+        if let Some(description) = &form.description {
+            let _ = writeln!(result, "<p>{}</p>", escape(description));
+        }
+
+        for section in &form.sections {
+            match &section.name {
+                Some(name) => {
+                    let _ = writeln!(result, "<h3>Abschnitt '{}'</h3>", escape(name));
+                }
+                None => result.push_str("<h3>Felder</h3>\n"),
+            }
+            if let Some(description) = &section.description {
+                let _ = writeln!(result, "<p>{}</p>", escape(description));
+            }
+
+            result.push_str("<table>\n<tr>");
+            for header in HEADERS {
+                let _ = write!(result, "<th>{header}</th>");
+            }
+            result.push_str("</tr>\n");
+            for field in &section.fields {
+                let _ = writeln!(
+                    result,
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    html_name(field),
+                    escape(&field.description),
+                    escape(&field.field_type),
+                    html_or_empty(field.data_catalogue_entry.as_ref()),
+                    escape(&field.mandatory),
+                    field
+                        .filter
+                        .as_ref()
+                        .map(|filter| format!("<code>{}</code>", escape(filter)))
+                        .unwrap_or_default(),
+                    html_or_empty(field.default_value.as_ref()),
+                    html_or_empty(field.hinweis.as_ref()),
+                );
+            }
+            result.push_str("</table>\n");
+
+            for field in &section.fields {
+                if let Some((catalogue, codes)) = &field.property_catalogue {
+                    let _ = writeln!(
+                        result,
+                        "<h4>Auswahlwerte für '{}' (Merkmalskatalog '{}')</h4>\n<ul>",
+                        escape(&field.name),
+                        escape(catalogue)
+                    );
+                    for (code, description) in codes {
+                        let _ = writeln!(
+                            result,
+                            "<li><code>{}</code>: {}</li>",
+                            escape(code),
+                            escape(description)
+                        );
+                    }
+                    result.push_str("</ul>\n");
+                }
+            }
+        }
+    }
+
+    result.push_str("</body>\n</html>\n");
+    result
+}
+
+/// Field name with link to the referenced form, if contained
+fn markdown_name(field: &FieldDoc) -> String {
+    match &field.reference {
+        Some(Reference {
+            name,
+            content_type: Some(content_type),
+        }) => format!(
+            "{} → [{}](#{})",
+            markdown(&field.name),
+            markdown(name),
+            anchor(*content_type, name)
+        ),
+        Some(reference) => format!("{} → {}", markdown(&field.name), markdown(&reference.name)),
+        None => markdown(&field.name),
+    }
+}
+
+/// Field name with link to the referenced form, if contained
+fn html_name(field: &FieldDoc) -> String {
+    match &field.reference {
+        Some(Reference {
+            name,
+            content_type: Some(content_type),
+        }) => format!(
+            "{} → <a href=\"#{}\">{}</a>",
+            escape(&field.name),
+            anchor(*content_type, name),
+            escape(name)
+        ),
+        Some(reference) => format!("{} → {}", escape(&field.name), escape(&reference.name)),
+        None => escape(&field.name).to_string(),
+    }
+}
+
+fn form_docs(data: &OnkostarEditor) -> Vec<FormDoc> {
+    let mut data_forms = data.editor.data_form.iter().collect::<Vec<_>>();
+    data_forms.sort_by_key(|form| form.get_name());
+    let mut unterformulare = data.editor.unterformular.iter().collect::<Vec<_>>();
+    unterformulare.sort_by_key(|form| form.get_name());
+
+    data_forms
+        .into_iter()
+        .map(|form| form_doc(data, form, ContentType::DataForm))
+        .chain(
+            unterformulare
+                .into_iter()
+                .map(|form| form_doc(data, form, ContentType::Unterformular)),
+        )
+        .collect()
+}
+
+fn form_doc<T>(data: &OnkostarEditor, form: &Form<T>, content_type: ContentType) -> FormDoc {
+    let mut entries = form.entries().iter().collect::<Vec<_>>();
+    entries.sort_by(|a, b| {
+        position(a)
+            .partial_cmp(&position(b))
+            .unwrap_or(Ordering::Equal)
+    });
+
+    let mut sections = vec![SectionDoc {
+        name: None,
+        description: None,
+        fields: vec![],
+    }];
+    let mut placed = vec![];
+    add_sections(data, form, &entries, None, &mut sections, &mut placed);
+    // Entries within a cycle of parents are not contained in any section
+    for entry in &entries {
+        if !placed.iter().any(|other| std::ptr::eq(*other, *entry)) {
+            sections[0].fields.push(field_doc(data, form, entry));
+        }
+    }
+    sections.retain(|section| !section.fields.is_empty());
+
+    FormDoc {
+        name: form.get_name(),
+        content_type,
+        description: form.description().map(ToString::to_string),
+        sections,
+    }
+}
+
+/// Adds fields of the given parent to the last section and a new section for each contained field group or section.
+/// Entries with an unknown parent are added to the top level section.
+fn add_sections<'a, T>(
+    data: &OnkostarEditor,
+    form: &Form<T>,
+    entries: &[&'a Entry],
+    parent: Option<&str>,
+    sections: &mut Vec<SectionDoc>,
+    placed: &mut Vec<&'a Entry>,
+) {
+    let is_known_parent = |name: &str| entries.iter().any(|entry| entry.get_name() == name);
+    let children = entries
+        .iter()
+        .filter(|entry| match parent {
+            Some(_) => entry.element_parent() == parent,
+            None => entry
+                .element_parent()
+                .is_none_or(|name| !is_known_parent(name)),
+        })
+        .filter(|entry| !placed.iter().any(|other| std::ptr::eq(*other, **entry)))
+        .copied()
+        .collect::<Vec<_>>();
+    placed.extend(&children);
+
+    let index = sections.len() - 1;
+    let mut groups = vec![];
+    for entry in children {
+        let name = entry.get_name();
+        if entries.iter().any(|other| {
+            other.element_parent() == Some(name.as_str())
+                && !placed.iter().any(|placed| std::ptr::eq(*placed, *other))
+        }) {
+            groups.push(entry);
+        } else {
+            sections[index].fields.push(field_doc(data, form, entry));
+        }
+    }
+
+    for group in groups {
+        let name = group.get_name();
+        sections.push(SectionDoc {
+            description: Some(group.description.clone()).filter(|d| !d.is_empty() && *d != name),
+            name: Some(name.clone()),
+            fields: vec![],
+        });
+        add_sections(data, form, entries, Some(name.as_str()), sections, placed);
+    }
+}
+
+fn field_doc<T>(data: &OnkostarEditor, form: &Form<T>, entry: &Entry) -> FieldDoc {
+    let reference = entry
+        .referenced_data_form
+        .as_ref()
+        .filter(|name| !name.is_empty() && (entry.is_form_reference() || entry.is_subform()))
+        .map(|name| {
+            let data_form = data.find_data_form(name).map(|_| ContentType::DataForm);
+            let unterformular = data
+                .find_unterformular(name)
+                .map(|_| ContentType::Unterformular);
+            Reference {
+                name: name.clone(),
+                // Subforms usually reference subforms, form references usually data forms
+                content_type: if entry.is_subform() {
+                    unterformular.or(data_form)
+                } else {
+                    data_form.or(unterformular)
+                },
+            }
+        });
+
+    FieldDoc {
+        name: entry.get_name(),
+        description: entry.description.clone(),
+        field_type: entry.entry_type().to_string(),
+        data_catalogue_entry: entry.data_catalogue_entry().map(ToString::to_string),
+        mandatory: match entry.mandatory() {
+            "mandatory" => "Ja".to_string(),
+            "false" | "" => "Nein".to_string(),
+            other => other.to_string(),
+        },
+        filter: entry
+            .filter
+            .as_ref()
+            .map(|filter| filter.condition.trim().to_string())
+            .filter(|condition| !condition.is_empty()),
+        default_value: entry.default_value().map(ToString::to_string),
+        hinweis: entry.hinweis.clone().filter(|hinweis| !hinweis.is_empty()),
+        reference,
+        property_catalogue: property_catalogue(data, form, entry),
+    }
+}
+
+/// Property catalogue used by the form field, either directly or by its data catalogue entry
+fn property_catalogue<T>(
+    data: &OnkostarEditor,
+    form: &Form<T>,
+    entry: &Entry,
+) -> Option<(String, Vec<(String, String)>)> {
+    let data_catalogue_entry = entry.data_catalogue_entry()?;
+
+    let name = match entry
+        .data_catalogue_entry_table()
+        .filter(|table| data.find_property_catalogue(table).is_some())
+    {
+        Some(table) => table.to_string(),
+        None => form
+            .data_catalogues()
+            .iter()
+            .filter_map(|name| data.find_data_catalogue(name))
+            .flat_map(DataCatalogue::entries)
+            .find(|item| item.get_name() == data_catalogue_entry)
+            .and_then(|item| item.property_catalogue())?
+            .to_string(),
+    };
+
+    let codes = data
+        .find_property_catalogue(&name)?
+        .codes()
+        .into_iter()
+        .map(|(code, description)| (code.to_string(), description.to_string()))
+        .collect::<Vec<_>>();
+
+    Some((name, codes)).filter(|(_, codes)| !codes.is_empty())
+}
+
+fn position(entry: &Entry) -> f64 {
+    entry.position.parse::<f64>().unwrap_or_default()
+}
+
+/// Anchor of a form, prefixed by its type to distinguish forms and subforms with the same name
+fn anchor(content_type: ContentType, name: &str) -> String {
+    let name = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>();
+    let prefix = match content_type {
+        ContentType::PropertyCatalogue => "merkmalskatalog",
+        ContentType::DataCatalogue => "datenkatalog",
+        ContentType::DataForm => "dataform",
+        ContentType::Unterformular => "unterformular",
+    };
+    format!("{prefix}-{name}")
+}
+
+fn markdown(value: &str) -> String {
+    value
+        .trim()
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+fn markdown_or_empty(value: Option<&String>) -> String {
+    value.map(|value| markdown(value)).unwrap_or_default()
+}
+
+fn html_or_empty(value: Option<&String>) -> String {
+    value
+        .map(|value| escape(value.trim()).to_string())
+        .unwrap_or_default()
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use model::osc::onkostar_editor::OnkostarEditor;
+
+    use model::osc::merge::ContentType;
+
+    use crate::doc::{anchor, to_html, to_markdown};
+
+    #[test]
+    fn should_render_markdown() {
+        let data = OnkostarEditor::from_str(include_str!("../libs/model/tests/test.osc")).unwrap();
+
+        let markdown = to_markdown(&data);
+
+        assert!(markdown.contains("## Formular: Hauptformular"));
+        assert!(markdown.contains("### Abschnitt 'Feldgruppe2'"));
+        assert!(markdown.contains(
+            "| Auswahl | Aktueller Wert | combobox | Auswahl | Ja | `getGlobalSetting('mehrere_mtb_in_mtbepisode') = 'true'` |  | Wählen Sie hier den Wert aus. |"
+        ));
+        assert!(markdown.contains("* `A`: A-Wert"));
+        assert!(markdown.contains("Unterformular → [Unterformular](#unterformular-unterformular)"));
+    }
+
+    #[test]
+    fn should_use_distinct_anchors_for_forms_and_subforms() {
+        assert_eq!(
+            anchor(ContentType::DataForm, "Befund 1"),
+            "dataform-befund-1"
+        );
+        assert_eq!(
+            anchor(ContentType::Unterformular, "Befund 1"),
+            "unterformular-befund-1"
+        );
+    }
+
+    #[test]
+    fn should_render_html() {
+        let data = OnkostarEditor::from_str(include_str!("../libs/model/tests/test.osc")).unwrap();
+
+        let html = to_html(&data);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h2 id=\"dataform-hauptformular\">Formular: Hauptformular</h2>"));
+        assert!(html.contains("<a href=\"#unterformular-unterformular\">Unterformular</a>"));
+        assert!(html.contains("<li><code>B</code>: B-Wert</li>"));
+    }
+
+    #[test]
+    fn should_render_fields_with_unknown_or_cyclic_parents() {
+        for parent in ["Unbekannt", "Button1"] {
+            let data =
+                OnkostarEditor::from_str(&include_str!("../libs/model/tests/test.osc").replacen(
+                    "<ElementParent>Feldgruppe2</ElementParent>",
+                    &format!("<ElementParent>{parent}</ElementParent>"),
+                    1,
+                ))
+                .unwrap();
+
+            let markdown = to_markdown(&data);
+
+            assert!(!markdown.contains("### Abschnitt 'Feldgruppe2'"));
+            assert!(markdown.contains("### Felder"));
+            assert!(markdown.contains("| Button1 | Testbutton | button |"));
+        }
+
+        // Field group containing a field with the same name
+        let data =
+            OnkostarEditor::from_str(&include_str!("../libs/model/tests/test.osc").replacen(
+                "<Name>Button1</Name>",
+                "<Name>Feldgruppe2</Name>",
+                1,
+            ))
+            .unwrap();
+
+        let markdown = to_markdown(&data);
+
+        assert!(markdown.contains("### Abschnitt 'Feldgruppe2'"));
+        assert!(markdown.contains("| Feldgruppe2 | Testbutton | button |"));
+    }
+}
@@ -0,0 +1,456 @@
+/*
+ * This file is part of osc-variant
+ *
+ * Copyright (C) 2026 the original author or authors.
+ *
+ * This program is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation; either version 2 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along
+ * with this program; if not, write to the Free Software Foundation, Inc.,
+ * 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
+ */
+
+//! Change report of `modify --dry-run`.
+//!
+//! All items are compared with a snapshot taken before the modification using the structural
+//! comparison of the model. Changes are categorized by the changed property.
+
+use std::fmt::{Display, Formatter};
+
+use console::style;
+use model::osc::Named;
+use model::osc::diff::{Change, diff};
+use model::osc::merge::ContentType;
+use model::osc::onkostar_editor::OnkostarEditor;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Content of all items before the modification
+pub struct Snapshot(Vec<(ContentType, String, Value)>);
+
+impl Snapshot {
+    pub fn new(data: &OnkostarEditor) -> Self {
+        Snapshot(items(data))
+    }
+}
+
+fn items(data: &OnkostarEditor) -> Vec<(ContentType, String, Value)> {
+    fn push_items(
+        content_type: ContentType,
+        list: &[impl Named + Serialize],
+        result: &mut Vec<(ContentType, String, Value)>,
+    ) {
+        for item in list {
+            result.push((
+                content_type,
+                item.get_name(),
+                serde_json::to_value(item).unwrap_or(Value::Null),
+            ));
+        }
+    }
+
+    let mut result = vec![];
+    push_items(
+        ContentType::PropertyCatalogue,
+        &data.editor.property_catalogue,
+        &mut result,
+    );
+    push_items(
+        ContentType::DataCatalogue,
+        &data.editor.data_catalogue,
+        &mut result,
+    );
+    push_items(ContentType::DataForm, &data.editor.data_form, &mut result);
+    push_items(
+        ContentType::Unterformular,
+        &data.editor.unterformular,
+        &mut result,
+    );
+    result
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ChangeKind {
+    FieldHidden,
+    FilterRemoved,
+    DefaultValueChanged,
+    ReferenceAdded,
+    ScriptReplaced,
+    MenuCategoryChanged,
+    NoticeUpdated,
+    MandatoryChanged,
+    ReadOnlyChanged,
+    ActiveChanged,
+    DescriptionChanged,
+    PositionChanged,
+    ElementParentChanged,
+    Other,
+}
+
+impl Display for ChangeKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ChangeKind::FieldHidden => "Formularfeld ausgeblendet",
+                ChangeKind::FilterRemoved => "Filter entfernt",
+                ChangeKind::DefaultValueChanged => "Standardwert geändert",
+                ChangeKind::ReferenceAdded => "Formularverweis hinzugefügt",
+                ChangeKind::ScriptReplaced => "Skript ersetzt",
+                ChangeKind::MenuCategoryChanged => "Menükategorie geändert",
+                ChangeKind::NoticeUpdated => "Ausfüllhinweis aktualisiert",
+                ChangeKind::MandatoryChanged => "Pflichtfeld geändert",
+                ChangeKind::ReadOnlyChanged => "Schreibschutz geändert",
+                ChangeKind::ActiveChanged => "Aktivierung geändert",
+                ChangeKind::DescriptionChanged => "Beschreibung geändert",
+                ChangeKind::PositionChanged => "Position geändert",
+                ChangeKind::ElementParentChanged => "Übergeordnetes Element geändert",
+                ChangeKind::Other => "Sonstige Änderung",
+            }
+        )
+    }
+}
+
+#[derive(PartialEq, Serialize)]
+struct ReportedChange {
+    kind: ChangeKind,
+    /// Changed element, e.g. a form field, `None` for the item itself
+    element: Option<String>,
+    property: Option<String>,
+    old: Option<String>,
+    new: Option<String>,
+}
+
+impl ReportedChange {
+    /// Categorizes a structural change, `None` for changes implied by other changes
+    fn from_change(change: Change) -> Option<Self> {
+        let (element, property, old, new) = match change {
+            Change::Added { element } => {
+                // Form added to the list of referenced forms
+                return Some(match element.split_once(" > DataFormReferences") {
+                    Some((entry, reference)) => ReportedChange {
+                        kind: ChangeKind::ReferenceAdded,
+                        element: Some(entry.to_string()),
+                        property: None,
+                        old: None,
+                        new: reference
+                            .rsplit_once("ReferencedDataForm '")
+                            .map(|(_, name)| name.trim_end_matches('\'').to_string()),
+                    },
+                    None => ReportedChange {
+                        kind: ChangeKind::Other,
+                        element: Some(element),
+                        property: None,
+                        old: None,
+                        new: None,
+                    },
+                });
+            }
+            Change::Removed { element } => {
+                return Some(ReportedChange {
+                    kind: ChangeKind::Other,
+                    element: Some(element),
+                    property: None,
+                    old: None,
+                    new: None,
+                });
+            }
+            Change::Changed {
+                element,
+                property,
+                old,
+                new,
+            } => (element, property, old, new),
+        };
+
+        let kind = match property.as_str() {
+            "Revision" | "Scripts.Valid" => return None,
+            "Filter.Condition" if new.as_deref() == Some("false") => ChangeKind::FieldHidden,
+            "Filter.Condition" if new.is_none() => ChangeKind::FilterRemoved,
+            "Filter.Condition" => ChangeKind::Other,
+            property if property.starts_with("Filter.") => return None,
+            "DefaultValue" => ChangeKind::DefaultValueChanged,
+            "ReferencedDataForm" => ChangeKind::ReferenceAdded,
+            "Scripts.Code" => ChangeKind::ScriptReplaced,
+            property if property.starts_with("MenuCategory.") => ChangeKind::MenuCategoryChanged,
+            "Hinweis" => ChangeKind::NoticeUpdated,
+            "Mandatory" => ChangeKind::MandatoryChanged,
+            "Readonly" => ChangeKind::ReadOnlyChanged,
+            "Active" => ChangeKind::ActiveChanged,
+            "Description" => ChangeKind::DescriptionChanged,
+            "Position" => ChangeKind::PositionChanged,
+            "ElementParent" => ChangeKind::ElementParentChanged,
+            _ => ChangeKind::Other,
+        };
+
+        Some(ReportedChange {
+            kind,
+            element,
+            property: Some(property),
+            old,
+            new,
+        })
+    }
+
+    fn is_same_reference(&self, other: &Self) -> bool {
+        self.kind == ChangeKind::ReferenceAdded
+            && other.kind == ChangeKind::ReferenceAdded
+            && self.element == other.element
+            && self.new == other.new
+    }
+
+    /// Saving is disabled when hiding a form field, this is implied by hiding the same element
+    fn is_implied_by_hiding(&self, hidden_elements: &[Option<String>]) -> bool {
+        self.property.as_deref() == Some("Speichern") && hidden_elements.contains(&self.element)
+    }
+}
+
+impl Display for ReportedChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if let Some(element) = &self.element {
+            write!(f, ": {element}")?;
+        }
+        match self.kind {
+            ChangeKind::FieldHidden
+            | ChangeKind::FilterRemoved
+            | ChangeKind::ScriptReplaced
+            | ChangeKind::NoticeUpdated => Ok(()),
+            ChangeKind::ReferenceAdded => match &self.new {
+                Some(new) => write!(f, " -> '{new}'"),
+                None => Ok(()),
+            },
+            _ => match &self.property {
+                Some(property) => write!(
+                    f,
+                    "{}{property}: '{}' -> '{}'",
+                    if self.element.is_some() { " " } else { ": " },
+                    self.old.as_deref().unwrap_or_default(),
+                    self.new.as_deref().unwrap_or_default()
+                ),
+                None => Ok(()),
+            },
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ItemReport {
+    #[serde(rename = "type")]
+    content_type: ContentType,
+    name: String,
+    /// Item removed from the file, e.g. by `--strip`
+    stripped: bool,
+    changes: Vec<ReportedChange>,
+}
+
+/// Changes of all items of a modified file
+#[derive(Serialize)]
+pub struct ChangeReport {
+    items: Vec<ItemReport>,
+}
+
+impl ChangeReport {
+    pub fn new(before: Snapshot, after: &OnkostarEditor) -> Self {
+        let after = items(after);
+        let mut items = vec![];
+
+        for (content_type, name, value) in before.0 {
+            let Some((_, _, new_value)) = after
+                .iter()
+                .find(|(other_type, other, _)| *other_type == content_type && *other == name)
+            else {
+                items.push(ItemReport {
+                    content_type,
+                    name,
+                    stripped: true,
+                    changes: vec![],
+                });
+                continue;
+            };
+
+            let mut changes: Vec<ReportedChange> = vec![];
+            for change in diff(&value, new_value)
+                .into_iter()
+                .filter_map(ReportedChange::from_change)
+            {
+                if !changes.iter().any(|other| other.is_same_reference(&change)) {
+                    changes.push(change);
+                }
+            }
+            let hidden_elements = changes
+                .iter()
+                .filter(|change| change.kind == ChangeKind::FieldHidden)
+                .map(|change| change.element.clone())
+                .collect::<Vec<_>>();
+            changes.retain(|change| !change.is_implied_by_hiding(&hidden_elements));
+
+            if !changes.is_empty() {
+                items.push(ItemReport {
+                    content_type,
+                    name,
+                    stripped: false,
+                    changes,
+                });
+            }
+        }
+
+        ChangeReport { items }
+    }
+
+    pub fn print(&self) {
+        if self.items.is_empty() {
+            println!("Keine Änderungen");
+            return;
+        }
+
+        for item in &self.items {
+            if item.stripped {
+                println!(
+                    "{} '{}' {}",
+                    item.content_type,
+                    style(&item.name).yellow(),
+                    style("wird entfernt").red()
+                );
+                continue;
+            }
+            println!("{} '{}'", item.content_type, style(&item.name).yellow());
+            for change in &item.changes {
+                println!("  {change}");
+            }
+        }
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use model::osc::onkostar_editor::OnkostarEditor;
+    use model::profile::Profile;
+
+    use crate::dry_run::{ChangeKind, ChangeReport, Snapshot};
+
+    #[test]
+    fn should_report_categorized_changes() {
+        let mut data =
+            OnkostarEditor::from_str(include_str!("../libs/model/tests/test.osc")).unwrap();
+        let profile = Profile::from_str(
+            "forms:
+               - name: 'Hauptformular'
+                 form_fields:
+                   - name: Auswahl
+                     hide: true
+                     default_value: 'B'
+            ",
+        )
+        .unwrap();
+
+        let snapshot = Snapshot::new(&data);
+        data.apply_profile(&profile);
+        data.editor.property_catalogue.clear();
+
+        let report = ChangeReport::new(snapshot, &data);
+
+        assert_eq!(report.items.len(), 2);
+        assert!(report.items[0].stripped);
+        assert_eq!(report.items[1].name, "Hauptformular");
+        let kinds = report.items[1]
+            .changes
+            .iter()
+            .map(|change| change.kind)
+            .collect::<Vec<_>>();
+        assert!(kinds.contains(&ChangeKind::FieldHidden));
+        assert!(kinds.contains(&ChangeKind::DefaultValueChanged));
+        assert!(
+            !report.items[1]
+                .changes
+                .iter()
+                .any(|change| change.property.as_deref() == Some("Speichern"))
+        );
+    }
+
+    #[test]
+    fn should_report_changed_form_field_attributes() {
+        let mut data =
+            OnkostarEditor::from_str(include_str!("../libs/model/tests/test.osc")).unwrap();
+        let profile = Profile::from_str(
+            "forms:
+               - name: 'Hauptformular'
+                 form_fields:
+                   - name: Auswahl
+                     mandatory: false
+                     readonly: true
+                     active: false
+                     description: 'Neue Beschreibung'
+                     position: 3.0
+                     element_parent: Feldgruppe2
+            ",
+        )
+        .unwrap();
+
+        let snapshot = Snapshot::new(&data);
+        data.apply_profile(&profile);
+
+        let report = ChangeReport::new(snapshot, &data);
+
+        let kinds = report.items[0]
+            .changes
+            .iter()
+            .map(|change| change.kind)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                ChangeKind::ActiveChanged,
+                ChangeKind::DescriptionChanged,
+                ChangeKind::ElementParentChanged,
+                ChangeKind::MandatoryChanged,
+                ChangeKind::PositionChanged,
+                ChangeKind::ReadOnlyChanged,
+            ]
+        );
+    }
+
+    #[test]
+    fn should_report_disabled_saving_of_visible_form_fields() {
+        let content = include_str!("../libs/model/tests/test.osc");
+        let data = OnkostarEditor::from_str(content).unwrap();
+        let modified = content
+            .lines()
+            .enumerate()
+            .map(|(index, line)| {
+                // Saving of form field 'Auswahl' in 'Hauptformular'
+                if index == 585 {
+                    line.replace("<Speichern>1</Speichern>", "<Speichern>0</Speichern>")
+                } else {
+                    line.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let report = ChangeReport::new(
+            Snapshot::new(&data),
+            &OnkostarEditor::from_str(&modified).unwrap(),
+        );
+
+        assert_eq!(report.items.len(), 1);
+        assert_eq!(report.items[0].changes.len(), 1);
+        assert_eq!(
+            report.items[0].changes[0].property.as_deref(),
+            Some("Speichern")
+        );
+    }
+}
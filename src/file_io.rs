@@ -25,9 +25,13 @@ use std::marker::PhantomData;
 use std::path::Path;
 use std::str::FromStr;
 
+use console::style;
 use model::osc::onkostar_editor::OnkostarEditor;
 use model::profile::Profile;
 
+use crate::checks::config::CheckConfig;
+use crate::library::Library;
+
 pub enum FileError {
     Reading(String, String),
     Writing(String, String),
@@ -91,6 +95,7 @@ impl InputFile {
     }
 
     #[allow(unused_variables)]
+    #[allow(clippy::needless_pass_by_value)]
     pub fn read(filename: String, password: Option<String>) -> Result<Self, FileError> {
         if let Some(extension) = Path::new(filename.as_str()).extension() {
             return match extension.to_str() {
@@ -163,6 +168,17 @@ impl InputFile {
 
         Err(FileError::Reading(filename, String::new()))
     }
+
+    /// Converts an OSC file into `OnkostarEditor` and keeps unknown elements if `lossless` is set
+    pub fn into_onkostar_editor(self, lossless: bool) -> Result<OnkostarEditor, FileError> {
+        match self {
+            InputFile::Osc { filename, content } if lossless => {
+                OnkostarEditor::from_str_lossless(&content)
+                    .map_err(|err| FileError::Parsing(filename, err))
+            }
+            _ => self.try_into(),
+        }
+    }
 }
 
 impl TryFrom<InputFile> for OnkostarEditor {
@@ -204,19 +220,170 @@ impl TryFrom<InputFile> for Profile {
     }
 }
 
-/// Shortcut methods for OSC and Profile files
+impl TryFrom<InputFile> for CheckConfig {
+    type Error = FileError;
+
+    fn try_from(value: InputFile) -> Result<Self, Self::Error> {
+        match value {
+            InputFile::Yaml { filename, content } => match CheckConfig::from_str(&content) {
+                Ok(config) => Ok(config),
+                Err(err) => Err(FileError::Parsing(filename, err)),
+            },
+            InputFile::Osc { filename, .. }
+            | InputFile::Osb { filename, .. }
+            | InputFile::Other { filename, .. } => Err(FileError::Parsing(
+                filename,
+                "Keine Konfigurationsdatei".to_string(),
+            )),
+        }
+    }
+}
+
+/// Shortcut methods for OSC, Profile and check configuration files
 pub struct FileReader<FileType> {
     file_type: PhantomData<FileType>,
 }
 
 impl FileReader<OnkostarEditor> {
-    pub fn read(filename: &str) -> Result<OnkostarEditor, FileError> {
-        TryInto::<OnkostarEditor>::try_into(InputFile::read(filename.to_string(), None)?)
+    pub fn read(filename: &str, lossless: bool) -> Result<OnkostarEditor, FileError> {
+        InputFile::read(filename.to_string(), None)?.into_onkostar_editor(lossless)
     }
 }
 
 impl FileReader<Profile> {
+    /// Reads the profile file and resolves all profiles it is based on
     pub fn read(filename: &str) -> Result<Profile, FileError> {
-        TryInto::<Profile>::try_into(InputFile::read(filename.to_string(), None)?)
+        TryInto::<Profile>::try_into(InputFile::read(filename.to_string(), None)?)?
+            .resolve(&canonical_name(Path::new(filename)), &load_profile)
+            .map_err(|err| FileError::Parsing(filename.to_string(), err))
+    }
+
+    /// Reads an embedded profile and resolves all profiles it is based on
+    pub fn read_embedded(name: &str) -> Result<Profile, FileError> {
+        Profile::embedded_profile(name)
+            .and_then(|profile| profile.resolve(name, &load_profile))
+            .map_err(|err| FileError::Parsing(name.to_string(), err))
+    }
+}
+
+/// Loads a profile used in `extends` without resolving its bases.
+/// Names containing a '.' are files relative to the profile file using it, others are embedded profiles.
+fn load_profile(name: &str, origin: &str) -> Result<(String, Profile), String> {
+    if !name.contains('.') {
+        return Ok((name.to_string(), Profile::embedded_profile(name)?));
+    }
+
+    let filename = canonical_name(&match Path::new(origin).parent() {
+        Some(dir) if Path::new(origin).is_file() => dir.join(name),
+        _ => Path::new(name).to_path_buf(),
+    });
+
+    let profile = InputFile::read(filename.clone(), None)
+        .and_then(TryInto::<Profile>::try_into)
+        .map_err(|err| err.to_string())?;
+    Ok((filename, profile))
+}
+
+/// Canonical file name, used to detect cycles independent of the path used to reference a file
+fn canonical_name(path: &Path) -> String {
+    fs::canonicalize(path)
+        .as_deref()
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+impl FileReader<Library> {
+    /// Reads all OSC files contained in the given directory.
+    /// Files are read in lossless mode, unknown elements do not prevent using a file as part of the library.
+    /// Files that cannot be read are skipped with a warning.
+    pub fn read(dirname: &str) -> Result<Library, FileError> {
+        let mut filenames = fs::read_dir(dirname)
+            .map_err(|err| FileError::Reading(dirname.to_string(), err.to_string()))?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.is_file()
+                    && path
+                        .extension()
+                        .is_some_and(|extension| extension.eq_ignore_ascii_case("osc"))
+            })
+            .collect::<Vec<_>>();
+        filenames.sort();
+
+        let mut library = Library::default();
+        for filename in filenames {
+            let filename = filename.to_string_lossy();
+            match FileReader::<OnkostarEditor>::read(&filename, true) {
+                Ok(data) => library.add(&filename, &data),
+                Err(err) => eprintln!(
+                    "{} {err}\nDie Datei wird in der Bibliothek nicht berücksichtigt.",
+                    style("Warnung:").yellow()
+                ),
+            }
+        }
+
+        Ok(library)
+    }
+}
+
+impl FileReader<CheckConfig> {
+    pub fn read(filename: &str) -> Result<CheckConfig, FileError> {
+        TryInto::<CheckConfig>::try_into(InputFile::read(filename.to_string(), None)?)
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use model::osc::merge::ContentType;
+    use model::profile::Profile;
+
+    use crate::file_io::FileReader;
+    use crate::library::Library;
+
+    #[test]
+    fn should_detect_cyclic_profiles_using_different_paths() {
+        let dir = std::env::temp_dir().join(format!("osc-variant-profiles-{}", std::process::id()));
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("a.yml"), "extends: ./a.yml\nforms: []\n").unwrap();
+        fs::write(dir.join("b.yml"), "extends: sub/../c.yml\nforms: []\n").unwrap();
+        fs::write(dir.join("c.yml"), "extends: ./b.yml\nforms: []\n").unwrap();
+
+        let result = |name: &str| {
+            FileReader::<Profile>::read(&dir.join(name).to_string_lossy())
+                .map(|_| ())
+                .unwrap_err()
+                .to_string()
+        };
+        let a = result("a.yml");
+        let b = result("b.yml");
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(a.contains("Zyklische Vererbung von Profilen"));
+        assert!(b.contains("Zyklische Vererbung von Profilen"));
+    }
+
+    #[test]
+    fn should_skip_unparsable_library_files() {
+        let dir = std::env::temp_dir().join(format!("osc-variant-library-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.osc"), "<kein OSC>").unwrap();
+        fs::write(
+            dir.join("b.osc"),
+            include_str!("../libs/model/tests/test.osc"),
+        )
+        .unwrap();
+
+        let library = FileReader::<Library>::read(&dir.to_string_lossy());
+        fs::remove_dir_all(&dir).unwrap();
+
+        let library = library.unwrap();
+        assert_eq!(
+            library.find(ContentType::DataForm, "Hauptformular").len(),
+            1
+        );
     }
 }
@@ -0,0 +1,383 @@
+/*
+ * This file is part of osc-variant
+ *
+ * Copyright (C) 2026 the original author or authors.
+ *
+ * This program is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation; either version 2 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along
+ * with this program; if not, write to the Free Software Foundation, Inc.,
+ * 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
+ */
+
+//! Requirement graph of catalogues and forms in Graphviz DOT or Mermaid format
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
+
+use model::osc::merge::ContentType;
+use model::osc::onkostar_editor::OnkostarEditor;
+use model::osc::requirements::{Requirement, Requires};
+use model::osc::{FolderContained, Named};
+
+struct Node {
+    content_type: ContentType,
+    name: String,
+    system_library: bool,
+    external: bool,
+}
+
+/// Requirement graph with edges from requiring to required items.
+/// Edge kinds are the markers used by `tree`: `+` data catalogue, `-` property catalogue,
+/// `>` form reference and `*` subform.
+pub struct Graph {
+    nodes: Vec<Node>,
+    edges: BTreeSet<(usize, usize, char)>,
+}
+
+impl Graph {
+    pub fn new(data: &OnkostarEditor) -> Self {
+        let mut graph = Graph {
+            nodes: vec![],
+            edges: BTreeSet::new(),
+        };
+        let mut index = HashMap::new();
+
+        for item in &data.editor.property_catalogue {
+            graph.add_node(&mut index, ContentType::PropertyCatalogue, item);
+        }
+        for item in &data.editor.data_catalogue {
+            graph.add_node(&mut index, ContentType::DataCatalogue, item);
+        }
+        for item in &data.editor.data_form {
+            graph.add_node(&mut index, ContentType::DataForm, item);
+        }
+        for item in &data.editor.unterformular {
+            graph.add_node(&mut index, ContentType::Unterformular, item);
+        }
+
+        for item in &data.editor.data_catalogue {
+            graph.add_edges(
+                &mut index,
+                &(ContentType::DataCatalogue, item.get_name()),
+                &item.get_required_entries(data),
+            );
+        }
+        for item in &data.editor.data_form {
+            graph.add_edges(
+                &mut index,
+                &(ContentType::DataForm, item.get_name()),
+                &item.get_required_entries(data),
+            );
+        }
+        for item in &data.editor.unterformular {
+            graph.add_edges(
+                &mut index,
+                &(ContentType::Unterformular, item.get_name()),
+                &item.get_required_entries(data),
+            );
+        }
+
+        graph
+    }
+
+    fn add_node(
+        &mut self,
+        index: &mut HashMap<(ContentType, String), usize>,
+        content_type: ContentType,
+        item: &(impl Named + FolderContained),
+    ) {
+        index.insert((content_type, item.get_name()), self.nodes.len());
+        self.nodes.push(Node {
+            content_type,
+            name: item.get_name(),
+            system_library: item.is_system_library_content(),
+            external: false,
+        });
+    }
+
+    fn add_edges(
+        &mut self,
+        index: &mut HashMap<(ContentType, String), usize>,
+        from: &(ContentType, String),
+        requirements: &[Requirement],
+    ) {
+        let Some(&from) = index.get(from) else {
+            return;
+        };
+
+        for requirement in requirements {
+            let (content_type, name, external, kind) = edge(requirement);
+            let to = *index
+                .entry((content_type, name.clone()))
+                .or_insert_with(|| {
+                    self.nodes.push(Node {
+                        content_type,
+                        name,
+                        system_library: false,
+                        external,
+                    });
+                    self.nodes.len() - 1
+                });
+            self.edges.insert((from, to, kind));
+        }
+    }
+
+    /// Restricts the graph to the given form or subform and all items it requires directly or indirectly
+    pub fn restrict_to(&mut self, root: &str) -> Result<(), String> {
+        let Some(root) = self.nodes.iter().position(|node| {
+            !node.external
+                && matches!(
+                    node.content_type,
+                    ContentType::DataForm | ContentType::Unterformular
+                )
+                && node.name == root
+        }) else {
+            return Err(format!("Formular '{root}' ist nicht enthalten"));
+        };
+
+        let mut reachable = BTreeSet::from([root]);
+        let mut pending = vec![root];
+        while let Some(node) = pending.pop() {
+            for &(_, to, _) in self.edges.iter().filter(|(from, _, _)| *from == node) {
+                if reachable.insert(to) {
+                    pending.push(to);
+                }
+            }
+        }
+
+        let ids = reachable
+            .iter()
+            .enumerate()
+            .map(|(new_id, &id)| (id, new_id))
+            .collect::<HashMap<_, _>>();
+        self.nodes = std::mem::take(&mut self.nodes)
+            .into_iter()
+            .enumerate()
+            .filter(|(id, _)| ids.contains_key(id))
+            .map(|(_, node)| node)
+            .collect();
+        self.edges = self
+            .edges
+            .iter()
+            .filter_map(|(from, to, kind)| Some((*ids.get(from)?, *ids.get(to)?, *kind)))
+            .collect();
+
+        Ok(())
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut result = String::from(
+            "digraph requirements {\n  rankdir=LR;\n  node [fontname=\"sans-serif\"];\n  edge [fontname=\"sans-serif\"];\n",
+        );
+
+        for (id, node) in self.nodes.iter().enumerate() {
+            let shape = match node.content_type {
+                ContentType::PropertyCatalogue => "ellipse",
+                ContentType::DataCatalogue => "cylinder",
+                ContentType::DataForm => "box",
+                ContentType::Unterformular => "component",
+            };
+            let mut style = vec![];
+            if node.system_library {
+                style.push("filled");
+            }
+            if node.external {
+                style.push("dashed");
+            }
+            let _ = write!(
+                result,
+                "  n{id} [label=\"{}\", shape={shape}",
+                dot_escape(&node.name)
+            );
+            if !style.is_empty() {
+                let _ = write!(result, ", style=\"{}\"", style.join(","));
+            }
+            if node.system_library {
+                result.push_str(", fillcolor=lightgrey");
+            }
+            result.push_str("];\n");
+        }
+
+        for (from, to, kind) in &self.edges {
+            let style = match kind {
+                '>' => ", style=dashed",
+                '*' => ", style=bold",
+                _ => "",
+            };
+            let _ = writeln!(result, "  n{from} -> n{to} [label=\"{kind}\"{style}];");
+        }
+
+        result.push_str("}\n");
+        result
+    }
+
+    pub fn to_mermaid(&self) -> String {
+        let mut result = String::from("flowchart LR\n");
+
+        for (id, node) in self.nodes.iter().enumerate() {
+            let name = mermaid_escape(&node.name);
+            let _ = match node.content_type {
+                ContentType::PropertyCatalogue => writeln!(result, "  n{id}([\"{name}\"])"),
+                ContentType::DataCatalogue => writeln!(result, "  n{id}[(\"{name}\")]"),
+                ContentType::DataForm => writeln!(result, "  n{id}[\"{name}\"]"),
+                ContentType::Unterformular => writeln!(result, "  n{id}(\"{name}\")"),
+            };
+        }
+
+        for (from, to, kind) in &self.edges {
+            let arrow = match kind {
+                '>' => "-.",
+                '*' => "==",
+                _ => "--",
+            };
+            let tail = match kind {
+                '>' => ".->",
+                '*' => "==>",
+                _ => "-->",
+            };
+            let _ = writeln!(result, "  n{from} {arrow} \"{kind}\" {tail} n{to}");
+        }
+
+        let system_library = self.class_members(|node| node.system_library);
+        if !system_library.is_empty() {
+            let _ = writeln!(
+                result,
+                "  classDef system fill:#d3d3d3\n  class {system_library} system"
+            );
+        }
+        let external = self.class_members(|node| node.external);
+        if !external.is_empty() {
+            let _ = writeln!(
+                result,
+                "  classDef external stroke-dasharray: 5 5\n  class {external} external"
+            );
+        }
+
+        result
+    }
+
+    fn class_members(&self, predicate: impl Fn(&Node) -> bool) -> String {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| predicate(node))
+            .map(|(id, _)| format!("n{id}"))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/// Content type, name, external flag and edge kind of the required item
+fn edge(requirement: &Requirement) -> (ContentType, String, bool, char) {
+    match requirement {
+        Requirement::PropertyCatalogue(item) => {
+            (ContentType::PropertyCatalogue, item.get_name(), false, '-')
+        }
+        Requirement::ExternalPropertyCatalogue(name) => {
+            (ContentType::PropertyCatalogue, name.clone(), true, '-')
+        }
+        Requirement::DataCatalogue(item) => {
+            (ContentType::DataCatalogue, item.get_name(), false, '+')
+        }
+        Requirement::ExternalDataCatalogue(name) => {
+            (ContentType::DataCatalogue, name.clone(), true, '+')
+        }
+        Requirement::DataFormReference(item) => {
+            (ContentType::DataForm, item.get_name(), false, '>')
+        }
+        Requirement::UnterformularReference(item) => {
+            (ContentType::Unterformular, item.get_name(), false, '>')
+        }
+        Requirement::ExternalDataFormReference(name) => {
+            (ContentType::DataForm, name.clone(), true, '>')
+        }
+        Requirement::ExternalUnterformularReference(name) => {
+            (ContentType::Unterformular, name.clone(), true, '>')
+        }
+        Requirement::DataFormSubform(item) => (ContentType::DataForm, item.get_name(), false, '*'),
+        Requirement::UnterformularSubform(item) => {
+            (ContentType::Unterformular, item.get_name(), false, '*')
+        }
+        Requirement::ExternalDataFormSubform(name) => {
+            (ContentType::DataForm, name.clone(), true, '*')
+        }
+        Requirement::ExternalUnterformularSubform(name) => {
+            (ContentType::Unterformular, name.clone(), true, '*')
+        }
+    }
+}
+
+fn dot_escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn mermaid_escape(value: &str) -> String {
+    value.replace('"', "#quot;")
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use model::osc::onkostar_editor::OnkostarEditor;
+
+    use crate::graph::Graph;
+
+    fn graph() -> Graph {
+        // Data catalogue entry 'Auswahl' uses the property catalogue
+        Graph::new(
+            &OnkostarEditor::from_str(&include_str!("../libs/model/tests/test.osc").replacen(
+                "<Name>Auswahl</Name>\n          <NameExport>",
+                "<PropertyCatalogue>TEST.Property</PropertyCatalogue>\n          <Name>Auswahl</Name>\n          <NameExport>",
+                1,
+            ))
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn should_render_dot() {
+        let dot = graph().to_dot();
+
+        assert!(dot.starts_with("digraph requirements {"));
+        assert!(dot.contains("n4 [label=\"Unterformular\", shape=component];"));
+        assert!(dot.contains("n3 -> n4 [label=\"*\", style=bold];"));
+        assert!(dot.contains("n1 -> n0 [label=\"-\"];"));
+        assert!(dot.contains(
+            "n0 [label=\"TEST.Property\", shape=ellipse, style=\"filled\", fillcolor=lightgrey];"
+        ));
+        assert!(dot.contains("[label=\"Anderes Formular\", shape=box, style=\"dashed\"];"));
+    }
+
+    #[test]
+    fn should_render_mermaid() {
+        let mermaid = graph().to_mermaid();
+
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("  n3[\"Hauptformular\"]"));
+        assert!(mermaid.contains("  n3 == \"*\" ==> n4"));
+        assert!(mermaid.contains("class n5 external"));
+    }
+
+    #[test]
+    fn should_restrict_graph_to_root() {
+        let mut graph = graph();
+        graph.restrict_to("Unterformular").unwrap();
+
+        let dot = graph.to_dot();
+        assert!(!dot.contains("Hauptformular"));
+        assert!(dot.contains("Unterformulardaten"));
+
+        assert!(graph.restrict_to("Unbekannt").is_err());
+    }
+}
@@ -0,0 +1,743 @@
+/*
+ * This file is part of osc-variant
+ *
+ * Copyright (C) 2026 the original author or authors.
+ *
+ * This program is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation; either version 2 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along
+ * with this program; if not, write to the Free Software Foundation, Inc.,
+ * 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
+ */
+
+//! Machine-readable JSON output of the subcommands `list`, `tree`, `diff` and `check`.
+//!
+//! Every document contains the `schema_version`. It is increased on incompatible changes,
+//! e.g. removed or renamed properties. New properties may be added without a new version.
+
+use std::cmp::Ordering;
+
+use model::osc::diff::{Change, diff};
+use model::osc::merge::ContentType;
+use model::osc::onkostar_editor::{InfoXML, OnkostarEditor};
+use model::osc::requirements::{Requirement, Requires};
+use model::osc::{Comparable, FolderContained, Named};
+use serde::Serialize;
+
+use crate::checks;
+use crate::checks::config::CheckConfig;
+use crate::checks::{CheckNotice, PROBLEMS};
+use crate::library::{Library, LibraryItem};
+
+/// Version of the JSON output schema
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct Document<T: Serialize> {
+    schema_version: u32,
+    #[serde(flatten)]
+    content: T,
+}
+
+pub fn to_json(content: impl Serialize) -> Result<String, serde_json::Error> {
+    serde_json::to_string_pretty(&Document {
+        schema_version: SCHEMA_VERSION,
+        content,
+    })
+}
+
+#[derive(Serialize)]
+pub struct FileInfo {
+    created: String,
+    application: String,
+    version: String,
+}
+
+impl From<&InfoXML> for FileInfo {
+    fn from(info_xml: &InfoXML) -> Self {
+        FileInfo {
+            created: info_xml.datum_xml.clone(),
+            application: info_xml.name.clone(),
+            version: info_xml.version.clone(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Item {
+    #[serde(rename = "type")]
+    content_type: ContentType,
+    name: String,
+    revision: u16,
+    system_library: bool,
+    hash: String,
+}
+
+impl Item {
+    fn new(content_type: ContentType, item: &(impl Comparable + FolderContained)) -> Self {
+        Item {
+            content_type,
+            name: item.get_name(),
+            revision: item.get_revision(),
+            system_library: item.is_system_library_content(),
+            hash: item.get_hash(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct List {
+    file: FileInfo,
+    items: Vec<Item>,
+}
+
+impl From<&OnkostarEditor> for List {
+    fn from(data: &OnkostarEditor) -> Self {
+        let editor = &data.editor;
+        let items = editor
+            .property_catalogue
+            .iter()
+            .map(|item| Item::new(ContentType::PropertyCatalogue, item))
+            .chain(
+                editor
+                    .data_catalogue
+                    .iter()
+                    .map(|item| Item::new(ContentType::DataCatalogue, item)),
+            )
+            .chain(
+                editor
+                    .data_form
+                    .iter()
+                    .map(|item| Item::new(ContentType::DataForm, item)),
+            )
+            .chain(
+                editor
+                    .unterformular
+                    .iter()
+                    .map(|item| Item::new(ContentType::Unterformular, item)),
+            )
+            .collect();
+
+        List {
+            file: FileInfo::from(&data.info_xml),
+            items,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Relation {
+    Catalogue,
+    Reference,
+    Subform,
+}
+
+#[derive(Serialize)]
+struct Dependency<'a> {
+    #[serde(rename = "type")]
+    content_type: ContentType,
+    name: String,
+    relation: Relation,
+    /// Required item is not contained in the file
+    external: bool,
+    /// Files of the library providing the external item, only available if a library is used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provided_by: Option<Vec<&'a LibraryItem>>,
+}
+
+impl<'a> Dependency<'a> {
+    fn new(requirement: &Requirement, library: Option<&'a Library>) -> Self {
+        let (content_type, relation, name, external) = match requirement {
+            Requirement::PropertyCatalogue(item) => (
+                ContentType::PropertyCatalogue,
+                Relation::Catalogue,
+                item.get_name(),
+                false,
+            ),
+            Requirement::DataCatalogue(item) => (
+                ContentType::DataCatalogue,
+                Relation::Catalogue,
+                item.get_name(),
+                false,
+            ),
+            Requirement::ExternalPropertyCatalogue(name) => (
+                ContentType::PropertyCatalogue,
+                Relation::Catalogue,
+                name.clone(),
+                true,
+            ),
+            Requirement::ExternalDataCatalogue(name) => (
+                ContentType::DataCatalogue,
+                Relation::Catalogue,
+                name.clone(),
+                true,
+            ),
+            Requirement::DataFormReference(item) => (
+                ContentType::DataForm,
+                Relation::Reference,
+                item.get_name(),
+                false,
+            ),
+            Requirement::UnterformularReference(item) => (
+                ContentType::Unterformular,
+                Relation::Reference,
+                item.get_name(),
+                false,
+            ),
+            Requirement::ExternalDataFormReference(name) => (
+                ContentType::DataForm,
+                Relation::Reference,
+                name.clone(),
+                true,
+            ),
+            Requirement::ExternalUnterformularReference(name) => (
+                ContentType::Unterformular,
+                Relation::Reference,
+                name.clone(),
+                true,
+            ),
+            Requirement::DataFormSubform(item) => (
+                ContentType::DataForm,
+                Relation::Subform,
+                item.get_name(),
+                false,
+            ),
+            Requirement::UnterformularSubform(item) => (
+                ContentType::Unterformular,
+                Relation::Subform,
+                item.get_name(),
+                false,
+            ),
+            Requirement::ExternalDataFormSubform(name) => {
+                (ContentType::DataForm, Relation::Subform, name.clone(), true)
+            }
+            Requirement::ExternalUnterformularSubform(name) => (
+                ContentType::Unterformular,
+                Relation::Subform,
+                name.clone(),
+                true,
+            ),
+        };
+
+        Dependency {
+            content_type,
+            name,
+            relation,
+            external,
+            provided_by: library
+                .and_then(|library| library.resolve(requirement))
+                .map(|items| items.iter().collect()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct TreeItem<'a> {
+    #[serde(flatten)]
+    item: Item,
+    requires: Vec<Dependency<'a>>,
+}
+
+impl<'a> TreeItem<'a> {
+    fn new(
+        content_type: ContentType,
+        item: &(impl Comparable + FolderContained + Requires),
+        all: &OnkostarEditor,
+        library: Option<&'a Library>,
+    ) -> Self {
+        TreeItem {
+            item: Item::new(content_type, item),
+            requires: item
+                .get_required_entries(all)
+                .iter()
+                .map(|requirement| Dependency::new(requirement, library))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Tree<'a> {
+    file: FileInfo,
+    items: Vec<TreeItem<'a>>,
+}
+
+impl<'a> Tree<'a> {
+    /// Creates the tree of `data`, resolving external requirements using the `library` if given
+    pub fn new(data: &OnkostarEditor, library: Option<&'a Library>) -> Self {
+        let editor = &data.editor;
+        let items = editor
+            .property_catalogue
+            .iter()
+            .map(|item| TreeItem {
+                item: Item::new(ContentType::PropertyCatalogue, item),
+                requires: vec![],
+            })
+            .chain(
+                editor
+                    .data_catalogue
+                    .iter()
+                    .map(|item| TreeItem::new(ContentType::DataCatalogue, item, data, library)),
+            )
+            .chain(
+                editor
+                    .data_form
+                    .iter()
+                    .map(|item| TreeItem::new(ContentType::DataForm, item, data, library)),
+            )
+            .chain(
+                editor
+                    .unterformular
+                    .iter()
+                    .map(|item| TreeItem::new(ContentType::Unterformular, item, data, library)),
+            )
+            .collect();
+
+        Tree {
+            file: FileInfo::from(&data.info_xml),
+            items,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum DiffStatus {
+    OnlyInA,
+    OnlyInB,
+    NewerInA,
+    NewerInB,
+    ContentDiffers,
+}
+
+#[derive(Serialize)]
+struct ItemDiff {
+    #[serde(rename = "type")]
+    content_type: ContentType,
+    name: String,
+    status: DiffStatus,
+    revision_a: Option<u16>,
+    revision_b: Option<u16>,
+    /// Structural changes, only available in strict mode
+    changes: Vec<Change>,
+}
+
+#[derive(Serialize)]
+pub struct Diff {
+    file_a: FileInfo,
+    file_b: FileInfo,
+    items: Vec<ItemDiff>,
+}
+
+impl Diff {
+    /// Creates the diff of both files, equal to the output of `PrintableDiff::print_diff`
+    pub fn new(data_a: &mut OnkostarEditor, data_b: &mut OnkostarEditor, strict: bool) -> Self {
+        data_a.sorted();
+        data_b.sorted();
+
+        let mut items = vec![];
+        item_diffs(
+            ContentType::PropertyCatalogue,
+            &data_a.editor.property_catalogue,
+            &data_b.editor.property_catalogue,
+            strict,
+            &mut items,
+        );
+        item_diffs(
+            ContentType::DataCatalogue,
+            &data_a.editor.data_catalogue,
+            &data_b.editor.data_catalogue,
+            strict,
+            &mut items,
+        );
+        item_diffs(
+            ContentType::DataForm,
+            &data_a.editor.data_form,
+            &data_b.editor.data_form,
+            strict,
+            &mut items,
+        );
+        item_diffs(
+            ContentType::Unterformular,
+            &data_a.editor.unterformular,
+            &data_b.editor.unterformular,
+            strict,
+            &mut items,
+        );
+
+        Diff {
+            file_a: FileInfo::from(&data_a.info_xml),
+            file_b: FileInfo::from(&data_b.info_xml),
+            items,
+        }
+    }
+}
+
+fn item_diffs(
+    content_type: ContentType,
+    list_a: &[impl Comparable + Serialize],
+    list_b: &[impl Comparable + Serialize],
+    strict: bool,
+    items: &mut Vec<ItemDiff>,
+) {
+    let item_diff = |name: String, status, revision_a, revision_b, changes| ItemDiff {
+        content_type,
+        name,
+        status,
+        revision_a,
+        revision_b,
+        changes,
+    };
+
+    for entry_b in list_b {
+        if !list_a.iter().any(|a| a.get_name() == entry_b.get_name()) {
+            items.push(item_diff(
+                entry_b.get_name(),
+                DiffStatus::OnlyInB,
+                None,
+                Some(entry_b.get_revision()),
+                vec![],
+            ));
+        }
+    }
+
+    for entry_a in list_a {
+        let Some(entry_b) = list_b.iter().find(|b| b.get_name() == entry_a.get_name()) else {
+            items.push(item_diff(
+                entry_a.get_name(),
+                DiffStatus::OnlyInA,
+                Some(entry_a.get_revision()),
+                None,
+                vec![],
+            ));
+            continue;
+        };
+
+        let status = match entry_a.get_revision().cmp(&entry_b.get_revision()) {
+            Ordering::Less => DiffStatus::NewerInB,
+            Ordering::Greater => DiffStatus::NewerInA,
+            Ordering::Equal if strict && entry_a.get_hash() != entry_b.get_hash() => {
+                DiffStatus::ContentDiffers
+            }
+            Ordering::Equal => continue,
+        };
+
+        items.push(item_diff(
+            entry_a.get_name(),
+            status,
+            Some(entry_a.get_revision()),
+            Some(entry_b.get_revision()),
+            if strict {
+                diff(entry_a, entry_b)
+            } else {
+                vec![]
+            },
+        ));
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Severity {
+    Error,
+    Warning,
+    Info,
+    Ok,
+}
+
+#[derive(Serialize)]
+struct Notice {
+    severity: Severity,
+    code: Option<String>,
+    description: String,
+    line: Option<usize>,
+    example: Option<String>,
+}
+
+impl From<&CheckNotice> for Notice {
+    fn from(check_notice: &CheckNotice) -> Self {
+        let notice = |severity, description: &String, line: &Option<usize>| Notice {
+            severity,
+            code: None,
+            description: description.clone(),
+            line: *line,
+            example: None,
+        };
+
+        match check_notice {
+            CheckNotice::ErrorWithCode {
+                code,
+                description,
+                line,
+                example,
+            } => Notice {
+                code: Some(code.clone()),
+                example: example.clone(),
+                ..notice(Severity::Error, description, line)
+            },
+            CheckNotice::WarningWithCode {
+                code,
+                description,
+                line,
+                example,
+            } => Notice {
+                code: Some(code.clone()),
+                example: example.clone(),
+                ..notice(Severity::Warning, description, line)
+            },
+            CheckNotice::InfoWithCode {
+                code,
+                description,
+                line,
+                example,
+            } => Notice {
+                code: Some(code.clone()),
+                example: example.clone(),
+                ..notice(Severity::Info, description, line)
+            },
+            CheckNotice::Error { description, line } => notice(Severity::Error, description, line),
+            CheckNotice::Warning { description, line } => {
+                notice(Severity::Warning, description, line)
+            }
+            CheckNotice::Info { description, line } => notice(Severity::Info, description, line),
+            CheckNotice::Ok(description) => notice(Severity::Ok, description, &None),
+            CheckNotice::Progress {
+                description,
+                severity,
+            } => notice((*severity).into(), description, &None),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Check {
+    file: String,
+    notices: Vec<Notice>,
+}
+
+impl Check {
+    pub fn new(file: &str, notices: &[CheckNotice]) -> Self {
+        Check {
+            file: file.to_string(),
+            notices: notices.iter().map(Notice::from).collect(),
+        }
+    }
+}
+
+impl From<checks::Severity> for Severity {
+    fn from(severity: checks::Severity) -> Self {
+        match severity {
+            checks::Severity::Error => Severity::Error,
+            checks::Severity::Warning => Severity::Warning,
+            checks::Severity::Info => Severity::Info,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct KnownProblem {
+    code: &'static str,
+    name: &'static str,
+    description: String,
+    fixable: bool,
+    /// Effective severity, `None` if the check is disabled
+    severity: Option<Severity>,
+}
+
+/// Known problems as listed by `check --list`
+#[derive(Serialize)]
+pub struct ProblemList {
+    problems: Vec<KnownProblem>,
+    target_version: Option<String>,
+    config_file: Option<String>,
+    ignore_forms: Vec<String>,
+}
+
+impl ProblemList {
+    pub fn new(config: &CheckConfig, config_file: Option<&str>) -> Self {
+        ProblemList {
+            problems: PROBLEMS
+                .iter()
+                .map(|problem| KnownProblem {
+                    code: problem.code,
+                    name: problem.name,
+                    description: problem
+                        .description
+                        .lines()
+                        .map(str::trim)
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                    fixable: problem.fixable,
+                    severity: (!config.is_disabled(problem.code))
+                        .then(|| Severity::from(config.severity_of(problem))),
+                })
+                .collect(),
+            target_version: config.target_version.as_ref().map(ToString::to_string),
+            config_file: config_file.map(ToString::to_string),
+            ignore_forms: config.ignore_forms.clone(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct PackageFile {
+    filename: String,
+    #[serde(flatten)]
+    list: List,
+}
+
+/// Contents of all OSC files of an OSB package
+#[derive(Serialize)]
+pub struct PackageList {
+    files: Vec<PackageFile>,
+}
+
+impl PackageList {
+    pub fn new(files: Vec<(String, &OnkostarEditor)>) -> Self {
+        PackageList {
+            files: files
+                .into_iter()
+                .map(|(filename, data)| PackageFile {
+                    filename,
+                    list: List::from(data),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use model::osc::onkostar_editor::OnkostarEditor;
+    use serde_json::Value;
+
+    use crate::checks::CheckNotice;
+    use crate::checks::config::CheckConfig;
+    use crate::json::{Check, Diff, List, PackageList, ProblemList, Tree, to_json};
+
+    fn parse(content: &str) -> OnkostarEditor {
+        OnkostarEditor::from_str(content).unwrap()
+    }
+
+    fn json(content: impl serde::Serialize) -> Value {
+        serde_json::from_str(&to_json(content).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn should_list_items_with_schema_version() {
+        let data = parse(include_str!("../libs/model/tests/test.osc"));
+
+        let json = json(List::from(&data));
+
+        assert_eq!(json["schema_version"], 1);
+        assert_eq!(json["file"]["application"], "OnkoStar");
+        assert_eq!(json["items"][3]["type"], "data_form");
+        assert_eq!(json["items"][3]["name"], "Hauptformular");
+        assert_eq!(json["items"][3]["revision"], 166);
+        assert_eq!(json["items"][3]["system_library"], false);
+    }
+
+    #[test]
+    fn should_contain_requirements_in_tree() {
+        let data = parse(include_str!("../libs/model/tests/test.osc"));
+
+        let json = json(Tree::new(&data, None));
+
+        let form = json["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|item| item["name"] == "Hauptformular")
+            .unwrap();
+        assert!(
+            form["requires"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|dependency| {
+                    dependency["name"] == "Unterformular"
+                        && dependency["relation"] == "subform"
+                        && dependency["external"] == false
+                })
+        );
+    }
+
+    #[test]
+    fn should_contain_changes_in_strict_diff() {
+        let data_a = &mut parse(include_str!("../libs/model/tests/test.osc"));
+        let data_b = &mut parse(&include_str!("../libs/model/tests/test.osc").replace(
+            "<Description>Test Hauptformular</Description>",
+            "<Description>Neu</Description>",
+        ));
+
+        assert!(
+            json(Diff::new(data_a, data_b, false))["items"]
+                .as_array()
+                .unwrap()
+                .is_empty()
+        );
+
+        let json = json(Diff::new(data_a, data_b, true));
+        assert_eq!(json["items"][0]["status"], "content_differs");
+        assert_eq!(json["items"][0]["changes"][0]["kind"], "changed");
+        assert_eq!(json["items"][0]["changes"][0]["property"], "Description");
+    }
+
+    #[test]
+    fn should_contain_check_notices() {
+        let notices = vec![
+            CheckNotice::ErrorWithCode {
+                code: "2023-0001".to_string(),
+                description: "Test".to_string(),
+                line: Some(42),
+                example: None,
+            },
+            CheckNotice::Warning {
+                description: "Test".to_string(),
+                line: None,
+            },
+        ];
+
+        let json = json(Check::new("test.osc", &notices));
+
+        assert_eq!(json["notices"][0]["severity"], "error");
+        assert_eq!(json["notices"][0]["code"], "2023-0001");
+        assert_eq!(json["notices"][0]["line"], 42);
+        assert_eq!(json["notices"][1]["severity"], "warning");
+        assert_eq!(json["notices"][1]["code"], Value::Null);
+    }
+
+    #[test]
+    fn should_list_known_problems() {
+        let config = CheckConfig::from_str("disabled:\n  - '2023-0001'").unwrap();
+
+        let json = json(ProblemList::new(&config, Some("osc-variant-check.yml")));
+
+        assert_eq!(json["problems"][0]["code"], "2023-0001");
+        assert_eq!(json["problems"][0]["severity"], Value::Null);
+        assert_eq!(json["problems"][1]["severity"], "error");
+        assert_eq!(json["config_file"], "osc-variant-check.yml");
+    }
+
+    #[test]
+    fn should_list_package_files() {
+        let data = parse(include_str!("../libs/model/tests/test.osc"));
+
+        let json = json(PackageList::new(vec![("test.osc".to_string(), &data)]));
+
+        assert_eq!(json["files"][0]["filename"], "test.osc");
+        assert_eq!(json["files"][0]["items"][3]["name"], "Hauptformular");
+    }
+}
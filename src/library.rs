@@ -0,0 +1,141 @@
+/*
+ * This file is part of osc-variant
+ *
+ * Copyright (C) 2026 the original author or authors.
+ *
+ * This program is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation; either version 2 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along
+ * with this program; if not, write to the Free Software Foundation, Inc.,
+ * 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
+ */
+
+//! Index of OSC files in a directory, used to resolve requirements not contained in a file
+
+use std::collections::HashMap;
+
+use model::osc::Comparable;
+use model::osc::merge::ContentType;
+use model::osc::onkostar_editor::OnkostarEditor;
+use model::osc::requirements::Requirement;
+use serde::Serialize;
+
+/// File of the library providing an item in the given revision
+#[derive(Debug, Serialize)]
+pub struct LibraryItem {
+    pub file: String,
+    pub revision: u16,
+}
+
+#[derive(Debug, Default)]
+pub struct Library {
+    items: HashMap<(ContentType, String), Vec<LibraryItem>>,
+}
+
+impl Library {
+    /// Adds all catalogues and forms contained in `data` as provided by `file`
+    pub fn add(&mut self, file: &str, data: &OnkostarEditor) {
+        let editor = &data.editor;
+        for item in &editor.property_catalogue {
+            self.add_item(file, ContentType::PropertyCatalogue, item);
+        }
+        for item in &editor.data_catalogue {
+            self.add_item(file, ContentType::DataCatalogue, item);
+        }
+        for item in &editor.data_form {
+            self.add_item(file, ContentType::DataForm, item);
+        }
+        for item in &editor.unterformular {
+            self.add_item(file, ContentType::Unterformular, item);
+        }
+    }
+
+    fn add_item(&mut self, file: &str, content_type: ContentType, item: &impl Comparable) {
+        self.items
+            .entry((content_type, item.get_name().to_lowercase()))
+            .or_default()
+            .push(LibraryItem {
+                file: file.to_string(),
+                revision: item.get_revision(),
+            });
+    }
+
+    /// All files providing an item, names are compared ignoring case
+    pub fn find(&self, content_type: ContentType, name: &str) -> &[LibraryItem] {
+        self.items
+            .get(&(content_type, name.to_lowercase()))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// All files providing a requirement not contained in the file itself.
+    /// Returns `None` for contained requirements.
+    pub fn resolve(&self, requirement: &Requirement) -> Option<&[LibraryItem]> {
+        external_requirement(requirement).map(|(content_type, name)| self.find(content_type, name))
+    }
+}
+
+/// Type and name of a requirement not contained in the file itself
+pub fn external_requirement<'a>(requirement: &'a Requirement) -> Option<(ContentType, &'a str)> {
+    match requirement {
+        Requirement::ExternalPropertyCatalogue(name) => {
+            Some((ContentType::PropertyCatalogue, name))
+        }
+        Requirement::ExternalDataCatalogue(name) => Some((ContentType::DataCatalogue, name)),
+        Requirement::ExternalDataFormReference(name)
+        | Requirement::ExternalDataFormSubform(name) => Some((ContentType::DataForm, name)),
+        Requirement::ExternalUnterformularReference(name)
+        | Requirement::ExternalUnterformularSubform(name) => {
+            Some((ContentType::Unterformular, name))
+        }
+        _ => None,
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use model::osc::merge::ContentType;
+    use model::osc::onkostar_editor::OnkostarEditor;
+    use model::osc::requirements::Requirement;
+
+    use crate::library::Library;
+
+    #[test]
+    fn should_resolve_external_requirements() {
+        let mut library = Library::default();
+        library.add(
+            "test.osc",
+            &OnkostarEditor::from_str(include_str!("../libs/model/tests/test.osc")).unwrap(),
+        );
+
+        let items = library
+            .resolve(&Requirement::ExternalDataFormReference(
+                "hauptformular".to_string(),
+            ))
+            .unwrap();
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].file, "test.osc");
+        assert!(
+            library
+                .resolve(&Requirement::ExternalDataCatalogue("Unbekannt".to_string()))
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            library
+                .find(ContentType::Unterformular, "Hauptformular")
+                .is_empty()
+        );
+    }
+}
@@ -27,14 +27,21 @@ mod checks;
 mod cli;
 mod commands;
 mod console;
+mod doc;
+mod dry_run;
 mod file_io;
+mod graph;
+mod json;
+mod library;
 mod notices;
+mod scripts;
+mod split;
 
 #[cfg(feature = "unzip-osb")]
 mod unzip_osb;
 
 fn main() -> Result<(), Box<dyn Error>> {
     let cli = Cli::parse();
-    handle(cli.cmd, cli.verbose)?;
+    handle(cli.cmd, cli.verbose, cli.lossless, cli.format)?;
     Ok(())
 }
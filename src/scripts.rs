@@ -0,0 +1,543 @@
+/*
+ * This file is part of osc-variant
+ *
+ * Copyright (C) 2026 the original author or authors.
+ *
+ * This program is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation; either version 2 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along
+ * with this program; if not, write to the Free Software Foundation, Inc.,
+ * 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
+ */
+
+//! Extraction of embedded scripts into standalone files and injection of edited files
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Component, Path};
+
+use model::osc::form::{Form, SCRIPT_EVENTS};
+use model::osc::merge::ContentType;
+use model::osc::onkostar_editor::OnkostarEditor;
+use model::osc::{Named, escape_script, unescape_script};
+use serde::{Deserialize, Serialize};
+
+use crate::file_io::FileError;
+use crate::split::{file_name, sanitized_name};
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ScriptKind {
+    FormEvent,
+    FormField,
+    Rskript,
+}
+
+/// Extracted script file with the item containing the script
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ManifestEntry {
+    file: String,
+    #[serde(rename = "type")]
+    kind: ScriptKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    form: Option<String>,
+    name: String,
+}
+
+/// List of all extracted scripts, used to detect added or removed script files
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    scripts: Vec<ManifestEntry>,
+}
+
+/// Writes all scripts of forms, form fields and R scripts into the given directory.
+/// Returns the number of written script files.
+pub fn extract(data: &OnkostarEditor, dirname: &str) -> Result<usize, FileError> {
+    let dir = Path::new(dirname);
+    fs::create_dir_all(dir).map_err(|err| writing_error(dir, &err))?;
+
+    // Remove previously extracted files to prevent stale scripts
+    if dir.join(MANIFEST_FILE).exists() {
+        for entry in read_manifest(dir)?.scripts {
+            let path = dir.join(&entry.file);
+            if path.exists() {
+                fs::remove_file(&path).map_err(|err| writing_error(&path, &err))?;
+            }
+        }
+    }
+
+    let scripts = scripts(data);
+    let mut files = HashSet::new();
+    for (entry, _) in &scripts {
+        if !files.insert(entry.file.to_lowercase()) {
+            return Err(writing_error(
+                &dir.join(&entry.file),
+                &format!("Mehrere Skripte mit Dateiname '{}'", entry.file),
+            ));
+        }
+    }
+
+    for (entry, code) in &scripts {
+        let path = dir.join(&entry.file);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|err| writing_error(parent, &err))?;
+        }
+        fs::write(&path, code).map_err(|err| writing_error(&path, &err))?;
+    }
+
+    let manifest = Manifest {
+        scripts: scripts.iter().map(|(entry, _)| entry.clone()).collect(),
+    };
+    let path = dir.join(MANIFEST_FILE);
+    let json = serde_json::to_string_pretty(&manifest).map_err(|err| writing_error(&path, &err))?;
+    fs::write(&path, json + "\n").map_err(|err| writing_error(&path, &err))?;
+
+    Ok(scripts.len())
+}
+
+/// Puts the script files of the given directory back into forms, form fields and R scripts.
+/// Files listed in the manifest but no longer present remove the script, new files add a script.
+/// Revisions of changed items are increased. Returns a description of each change.
+pub fn inject(data: &mut OnkostarEditor, dirname: &str) -> Result<Vec<String>, FileError> {
+    let dir = Path::new(dirname);
+    let manifest = read_manifest(dir)?;
+
+    let mut pending = vec![];
+    for entry in &manifest.scripts {
+        let path = dir.join(&entry.file);
+        let code = if path.exists() {
+            Some(read_script(&path)?)
+        } else {
+            None
+        };
+        pending.push((entry.clone(), code, false));
+    }
+
+    for file in script_files(dir)? {
+        if manifest.scripts.iter().any(|entry| entry.file == file) {
+            continue;
+        }
+        let Some(entry) = resolve(data, &file) else {
+            return Err(FileError::Reading(
+                dir.join(&file).to_string_lossy().to_string(),
+                "Kein passendes Formular, Formularfeld oder R-Skript gefunden".to_string(),
+            ));
+        };
+        pending.push((entry, Some(read_script(&dir.join(&file))?), true));
+    }
+
+    let mut changes = vec![];
+    let mut updated_forms = HashSet::new();
+    for (entry, code, added) in pending {
+        let (updated, content_type) = update(data, &entry, code.as_deref()).ok_or_else(|| {
+            FileError::Reading(
+                dir.join(&entry.file).to_string_lossy().to_string(),
+                format!(
+                    "'{}' ist nicht enthalten",
+                    entry.form.as_ref().unwrap_or(&entry.name)
+                ),
+            )
+        })?;
+        if updated {
+            if let (Some(content_type), Some(form)) = (content_type, entry.form) {
+                updated_forms.insert((content_type, form));
+            }
+            changes.push(match (code, added) {
+                (None, _) => format!("Skript '{}' entfernt", entry.file),
+                (Some(_), true) => format!("Skript '{}' hinzugefügt", entry.file),
+                (Some(_), false) => format!("Skript '{}' geändert", entry.file),
+            });
+        }
+    }
+
+    // Increase the revision of each changed form only once
+    for form in &mut data.editor.data_form {
+        if updated_forms.contains(&(ContentType::DataForm, form.get_name())) {
+            form.revision += 1;
+        }
+    }
+    for form in &mut data.editor.unterformular {
+        if updated_forms.contains(&(ContentType::Unterformular, form.get_name())) {
+            form.revision += 1;
+        }
+    }
+
+    Ok(changes)
+}
+
+/// All scripts contained in the given content with unescaped code
+fn scripts(data: &OnkostarEditor) -> Vec<(ManifestEntry, String)> {
+    let mut result = vec![];
+
+    let forms = data
+        .editor
+        .data_form
+        .iter()
+        .map(|form| (form.get_name(), form.event_scripts(), form.entries()))
+        .chain(
+            data.editor
+                .unterformular
+                .iter()
+                .map(|form| (form.get_name(), form.event_scripts(), form.entries())),
+        );
+
+    for (form, event_scripts, entries) in forms {
+        let dir = sanitized_name(&form);
+        for (event, script) in event_scripts {
+            result.push((
+                ManifestEntry {
+                    file: format!("{dir}/{}", file_name(event, "js")),
+                    kind: ScriptKind::FormEvent,
+                    form: Some(form.clone()),
+                    name: event.to_string(),
+                },
+                unescape_script(script.code()),
+            ));
+        }
+        for entry in entries {
+            if let Some(ref script) = entry.scripts {
+                result.push((
+                    ManifestEntry {
+                        file: format!("{dir}/{}", file_name(&entry.get_name(), "js")),
+                        kind: ScriptKind::FormField,
+                        form: Some(form.clone()),
+                        name: entry.get_name(),
+                    },
+                    unescape_script(script.code()),
+                ));
+            }
+        }
+    }
+
+    for rskript in data.editor.rskript.iter().flatten() {
+        if let Some(ref code) = rskript.skript {
+            result.push((
+                ManifestEntry {
+                    file: file_name(&rskript.name, "R"),
+                    kind: ScriptKind::Rskript,
+                    form: None,
+                    name: rskript.name.clone(),
+                },
+                unescape_script(code),
+            ));
+        }
+    }
+
+    result
+}
+
+/// Finds the form event, form field or R script matching a script file not listed in the manifest
+fn resolve(data: &OnkostarEditor, file: &str) -> Option<ManifestEntry> {
+    let Some((dir, file_part)) = file.split_once('/') else {
+        return data
+            .editor
+            .rskript
+            .iter()
+            .flatten()
+            .find(|rskript| file_name(&rskript.name, "R") == file)
+            .map(|rskript| ManifestEntry {
+                file: file.to_string(),
+                kind: ScriptKind::Rskript,
+                form: None,
+                name: rskript.name.clone(),
+            });
+    };
+
+    let (form, entries) = data
+        .editor
+        .data_form
+        .iter()
+        .map(|form| (form.get_name(), form.entries()))
+        .chain(
+            data.editor
+                .unterformular
+                .iter()
+                .map(|form| (form.get_name(), form.entries())),
+        )
+        .find(|(form, _)| sanitized_name(form) == dir)?;
+
+    if let Some(event) = SCRIPT_EVENTS
+        .iter()
+        .find(|event| file_name(event, "js") == file_part)
+    {
+        return Some(ManifestEntry {
+            file: file.to_string(),
+            kind: ScriptKind::FormEvent,
+            form: Some(form),
+            name: (*event).to_string(),
+        });
+    }
+
+    entries
+        .iter()
+        .find(|entry| file_name(&entry.get_name(), "js") == file_part)
+        .map(|entry| ManifestEntry {
+            file: file.to_string(),
+            kind: ScriptKind::FormField,
+            form: Some(form),
+            name: entry.get_name(),
+        })
+}
+
+/// Updates the script and the revision of a changed form field or R script.
+/// Returns whether the script has been changed and the type of the containing form, if any.
+/// Returns `None` if the item is not contained.
+fn update(
+    data: &mut OnkostarEditor,
+    entry: &ManifestEntry,
+    code: Option<&str>,
+) -> Option<(bool, Option<ContentType>)> {
+    if entry.kind == ScriptKind::Rskript {
+        let rskript = data
+            .editor
+            .rskript
+            .iter_mut()
+            .flatten()
+            .find(|rskript| rskript.name == entry.name)?;
+        if rskript.skript.as_deref().map(unescape_script).as_deref() == code {
+            return Some((false, None));
+        }
+        rskript.skript = code.map(escape_script);
+        if let Some(ref mut revision) = rskript.revision {
+            *revision += 1;
+        }
+        return Some((true, None));
+    }
+
+    let form_name = entry.form.as_deref()?;
+    if let Some(form) = data
+        .editor
+        .data_form
+        .iter_mut()
+        .find(|form| form.get_name() == form_name)
+    {
+        return update_form(form, entry, code)
+            .map(|updated| (updated, Some(ContentType::DataForm)));
+    }
+    let form = data
+        .editor
+        .unterformular
+        .iter_mut()
+        .find(|form| form.get_name() == form_name)?;
+    update_form(form, entry, code).map(|updated| (updated, Some(ContentType::Unterformular)))
+}
+
+fn update_form<T>(form: &mut Form<T>, entry: &ManifestEntry, code: Option<&str>) -> Option<bool> {
+    match entry.kind {
+        ScriptKind::FormEvent => form.update_event_script(&entry.name, code),
+        _ => form.update_entry_script(&entry.name, code),
+    }
+}
+
+/// Relative paths of R scripts in the directory and scripts in form directories
+fn script_files(dir: &Path) -> Result<Vec<String>, FileError> {
+    let mut result = vec![];
+
+    for entry in fs::read_dir(dir).map_err(|err| reading_error(dir, &err))? {
+        let path = entry.map_err(|err| reading_error(dir, &err))?.path();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if path.is_file() && path.extension().is_some_and(|extension| extension == "R") {
+            result.push(name);
+        } else if path.is_dir() {
+            for entry in fs::read_dir(&path).map_err(|err| reading_error(&path, &err))? {
+                let file = entry.map_err(|err| reading_error(&path, &err))?.path();
+                if file.is_file() && file.extension().is_some_and(|extension| extension == "js") {
+                    result.push(format!(
+                        "{name}/{}",
+                        file.file_name()
+                            .map(|name| name.to_string_lossy().to_string())
+                            .unwrap_or_default()
+                    ));
+                }
+            }
+        }
+    }
+
+    result.sort();
+    Ok(result)
+}
+
+/// Reads the manifest, only accepting files within the given directory
+fn read_manifest(dir: &Path) -> Result<Manifest, FileError> {
+    let path = dir.join(MANIFEST_FILE);
+    let json = fs::read_to_string(&path).map_err(|err| reading_error(&path, &err))?;
+    let manifest = serde_json::from_str::<Manifest>(&json)
+        .map_err(|err| FileError::Parsing(path.to_string_lossy().to_string(), err.to_string()))?;
+
+    if let Some(entry) = manifest
+        .scripts
+        .iter()
+        .find(|entry| !is_relative_file(&entry.file))
+    {
+        return Err(FileError::Parsing(
+            path.to_string_lossy().to_string(),
+            format!(
+                "Die Datei '{}' liegt nicht innerhalb des Verzeichnisses",
+                entry.file
+            ),
+        ));
+    }
+
+    Ok(manifest)
+}
+
+/// Checks if the file is a plain relative path without any parent or root components
+fn is_relative_file(file: &str) -> bool {
+    let path = Path::new(file);
+    path.components().next().is_some()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+fn read_script(path: &Path) -> Result<String, FileError> {
+    fs::read_to_string(path)
+        .map(|code| code.replace("\r\n", "\n"))
+        .map_err(|err| reading_error(path, &err))
+}
+
+fn writing_error(path: &Path, err: &impl ToString) -> FileError {
+    FileError::Writing(path.to_string_lossy().to_string(), err.to_string())
+}
+
+fn reading_error(path: &Path, err: &impl ToString) -> FileError {
+    FileError::Reading(path.to_string_lossy().to_string(), err.to_string())
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::str::FromStr;
+
+    use model::osc::Named;
+    use model::osc::onkostar_editor::OnkostarEditor;
+
+    use crate::scripts::{extract, inject};
+
+    #[test]
+    fn should_extract_and_inject_scripts() {
+        let dir = std::env::temp_dir().join(format!("osc-variant-scripts-{}", std::process::id()));
+        let dirname = dir.to_string_lossy().to_string();
+
+        let mut data =
+            OnkostarEditor::from_str(include_str!("../libs/model/tests/test.osc")).unwrap();
+        assert_eq!(extract(&data, &dirname).unwrap(), 1);
+
+        let script = dir.join("Hauptformular").join("Formularverweis.js");
+        assert_eq!(fs::read_to_string(&script).unwrap(), "/* test */");
+        assert!(inject(&mut data, &dirname).unwrap().is_empty());
+
+        fs::write(&script, "// a\r\nb();").unwrap();
+        fs::write(
+            dir.join("Hauptformular").join("ScriptBeimSpeichern.js"),
+            "c();",
+        )
+        .unwrap();
+        let changes = inject(&mut data, &dirname).unwrap();
+
+        fs::remove_file(&script).unwrap();
+        let removed = inject(&mut data, &dirname).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            changes,
+            vec![
+                "Skript 'Hauptformular/Formularverweis.js' geändert",
+                "Skript 'Hauptformular/ScriptBeimSpeichern.js' hinzugefügt"
+            ]
+        );
+        assert_eq!(
+            removed,
+            vec!["Skript 'Hauptformular/Formularverweis.js' entfernt"]
+        );
+
+        let form = &data.editor.data_form[0];
+        let scripts = form.scripts();
+        assert_eq!(scripts.len(), 1);
+        assert_eq!(scripts[0].1.code(), "c();");
+        assert_eq!(form.revision, 168);
+    }
+
+    #[test]
+    fn should_only_increase_revision_of_form_containing_the_script() {
+        let dir = std::env::temp_dir().join(format!(
+            "osc-variant-scripts-same-name-{}",
+            std::process::id()
+        ));
+        let dirname = dir.to_string_lossy().to_string();
+
+        // Subform with the same name as the form containing the script
+        let mut data =
+            OnkostarEditor::from_str(&include_str!("../libs/model/tests/test.osc").replacen(
+                "      <Name>Unterformular</Name>",
+                "      <Name>Hauptformular</Name>",
+                1,
+            ))
+            .unwrap();
+        assert_eq!(data.editor.unterformular[0].get_name(), "Hauptformular");
+        let form_revision = data.editor.data_form[0].revision;
+        let subform_revision = data.editor.unterformular[0].revision;
+
+        extract(&data, &dirname).unwrap();
+        fs::write(dir.join("Hauptformular").join("Formularverweis.js"), "b();").unwrap();
+        let changes = inject(&mut data, &dirname).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(data.editor.data_form[0].revision, form_revision + 1);
+        assert_eq!(data.editor.unterformular[0].revision, subform_revision);
+    }
+
+    #[test]
+    fn should_reject_manifest_files_outside_of_directory() {
+        let dir = std::env::temp_dir().join(format!(
+            "osc-variant-scripts-manifest-{}",
+            std::process::id()
+        ));
+        let dirname = dir.to_string_lossy().to_string();
+        let outside = std::env::temp_dir().join(format!(
+            "osc-variant-scripts-outside-{}.js",
+            std::process::id()
+        ));
+        fs::write(&outside, "a();").unwrap();
+
+        let mut data =
+            OnkostarEditor::from_str(include_str!("../libs/model/tests/test.osc")).unwrap();
+
+        for file in [
+            "../Hauptformular/Formularverweis.js".to_string(),
+            outside.to_string_lossy().to_string(),
+        ] {
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(
+                dir.join("manifest.json"),
+                serde_json::json!({
+                    "scripts": [{"file": file, "type": "form_field", "form": "Hauptformular", "name": "Formularverweis"}]
+                })
+                .to_string(),
+            )
+            .unwrap();
+
+            assert!(extract(&data, &dirname).is_err());
+            assert!(inject(&mut data, &dirname).is_err());
+        }
+
+        assert!(outside.exists());
+        fs::remove_file(&outside).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,304 @@
+/*
+ * This file is part of osc-variant
+ *
+ * Copyright (C) 2026 the original author or authors.
+ *
+ * This program is free software; you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation; either version 2 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along
+ * with this program; if not, write to the Free Software Foundation, Inc.,
+ * 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
+ */
+
+//! Split OSC content into a directory tree with one file per catalogue or form and join it back
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use bundles::item_to_json;
+use model::osc::Named;
+use model::osc::lossless::UnknownElement;
+use model::osc::onkostar_editor::{Editor, InfoXML, OnkostarEditor};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use crate::file_io::FileError;
+
+const INFO_FILE: &str = "info.json";
+const EDITOR_FILE: &str = "editor.json";
+/// Key of unknown elements kept in lossless mode within the editor file
+const UNKNOWN_ELEMENTS: &str = "unknown_elements";
+
+/// Directories used for each type of content and the matching element name in `Editor`
+const CONTENT_DIRS: [(&str, &str); 4] = [
+    ("property-catalogues", "PropertyCatalogue"),
+    ("data-catalogues", "DataCatalogue"),
+    ("unterformulare", "Unterformular"),
+    ("data-forms", "DataForm"),
+];
+
+/// Writes `InfoXML`, other editor content and one file per catalogue and form into the given directory.
+/// Unknown elements kept in lossless mode are written into the editor file.
+/// Existing JSON files in content directories will be removed to prevent stale content.
+pub fn split(data: &mut OnkostarEditor, dirname: &str) -> Result<(), FileError> {
+    data.sorted();
+
+    let dir = Path::new(dirname);
+    fs::create_dir_all(dir).map_err(|err| writing_error(dir, &err))?;
+    write_file(&dir.join(INFO_FILE), &data.info_xml)?;
+
+    let Ok(Value::Object(mut editor)) = serde_json::to_value(&data.editor) else {
+        return Err(writing_error(dir, &"Inhalt kann nicht serialisiert werden"));
+    };
+    for (_, element) in CONTENT_DIRS {
+        editor.remove(element);
+    }
+    if !data.unknown_elements.is_empty() {
+        let Ok(unknown_elements) = serde_json::to_value(&data.unknown_elements) else {
+            return Err(writing_error(
+                dir,
+                &"Unbekannte Elemente können nicht serialisiert werden",
+            ));
+        };
+        editor.insert(UNKNOWN_ELEMENTS.to_string(), unknown_elements);
+    }
+    let editor_file = dir.join(EDITOR_FILE);
+    if editor.is_empty() {
+        if editor_file.exists() {
+            fs::remove_file(&editor_file).map_err(|err| writing_error(&editor_file, &err))?;
+        }
+    } else {
+        write_file(&editor_file, &editor)?;
+    }
+
+    write_items(
+        &dir.join(CONTENT_DIRS[0].0),
+        &data.editor.property_catalogue,
+    )?;
+    write_items(&dir.join(CONTENT_DIRS[1].0), &data.editor.data_catalogue)?;
+    write_items(&dir.join(CONTENT_DIRS[2].0), &data.editor.unterformular)?;
+    write_items(&dir.join(CONTENT_DIRS[3].0), &data.editor.data_form)?;
+
+    Ok(())
+}
+
+/// Reassembles OSC content from a directory created by `split`.
+/// Contents will be sorted by name and requirements, returning found requirement cycles.
+pub fn join(dirname: &str) -> Result<(OnkostarEditor, Vec<Vec<String>>), FileError> {
+    let dir = Path::new(dirname);
+
+    let info_xml = read_file::<InfoXML>(&dir.join(INFO_FILE))?;
+
+    let editor_file = dir.join(EDITOR_FILE);
+    let mut editor = if editor_file.exists() {
+        read_file::<Map<String, Value>>(&editor_file)?
+    } else {
+        Map::new()
+    };
+    let unknown_elements = match editor.remove(UNKNOWN_ELEMENTS) {
+        Some(value) => serde_json::from_value::<Vec<UnknownElement>>(value).map_err(|err| {
+            FileError::Parsing(editor_file.to_string_lossy().to_string(), err.to_string())
+        })?,
+        None => vec![],
+    };
+    for (subdir, element) in CONTENT_DIRS {
+        editor.insert(
+            element.to_string(),
+            Value::Array(read_items(&dir.join(subdir))?),
+        );
+    }
+    let editor = serde_json::from_value::<Editor>(Value::Object(editor))
+        .map_err(|err| FileError::Parsing(dirname.to_string(), err.to_string()))?;
+
+    let mut data = OnkostarEditor {
+        info_xml,
+        editor,
+        unknown_elements,
+    };
+    let cycles = data.sorted();
+
+    Ok((data, cycles))
+}
+
+/// Replaces all characters not usable in file names and leading dots with an underscore
+pub fn sanitized_name(name: &str) -> String {
+    let name = name.trim();
+    let leading_dots = name.len() - name.trim_start_matches('.').len();
+    "_".repeat(leading_dots)
+        + &name[leading_dots..]
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect::<String>()
+}
+
+/// Sanitized name with the given extension
+pub fn file_name(name: &str, extension: &str) -> String {
+    format!("{}.{extension}", sanitized_name(name))
+}
+
+fn writing_error(path: &Path, err: &impl ToString) -> FileError {
+    FileError::Writing(path.to_string_lossy().to_string(), err.to_string())
+}
+
+fn reading_error(path: &Path, err: &impl ToString) -> FileError {
+    FileError::Reading(path.to_string_lossy().to_string(), err.to_string())
+}
+
+fn write_file<T: Serialize>(path: &Path, item: &T) -> Result<(), FileError> {
+    let json = item_to_json(item).map_err(|err| writing_error(path, &err))?;
+    fs::write(path, json + "\n").map_err(|err| writing_error(path, &err))
+}
+
+fn read_file<T: DeserializeOwned>(path: &Path) -> Result<T, FileError> {
+    let json = fs::read_to_string(path).map_err(|err| reading_error(path, &err))?;
+    serde_json::from_str::<T>(&json)
+        .map_err(|err| FileError::Parsing(path.to_string_lossy().to_string(), err.to_string()))
+}
+
+fn json_files(dir: &Path) -> Result<Vec<PathBuf>, FileError> {
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut paths = fs::read_dir(dir)
+        .map_err(|err| reading_error(dir, &err))?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+    Ok(paths)
+}
+
+fn write_items<T: Named + Serialize>(dir: &Path, items: &[T]) -> Result<(), FileError> {
+    for path in json_files(dir)? {
+        fs::remove_file(&path).map_err(|err| writing_error(&path, &err))?;
+    }
+
+    if items.is_empty() {
+        return Ok(());
+    }
+
+    fs::create_dir_all(dir).map_err(|err| writing_error(dir, &err))?;
+
+    let mut file_names = Vec::<String>::new();
+    for item in items {
+        let file_name = file_name(&item.get_name(), "json");
+        if file_names
+            .iter()
+            .any(|existing| existing.eq_ignore_ascii_case(&file_name))
+        {
+            return Err(writing_error(
+                &dir.join(&file_name),
+                &format!(
+                    "Mehrere Inhalte mit Dateiname '{file_name}' für '{}'",
+                    item.get_name()
+                ),
+            ));
+        }
+        write_file(&dir.join(&file_name), item)?;
+        file_names.push(file_name);
+    }
+
+    Ok(())
+}
+
+fn read_items(dir: &Path) -> Result<Vec<Value>, FileError> {
+    json_files(dir)?
+        .iter()
+        .map(|path| read_file::<Value>(path))
+        .collect()
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::str::FromStr;
+
+    use model::osc::onkostar_editor::OnkostarEditor;
+
+    use crate::split::{file_name, join, sanitized_name, split};
+
+    #[test]
+    fn should_replace_invalid_characters_in_file_name() {
+        assert_eq!(file_name("ONK.Form / Test", "json"), "ONK.Form___Test.json");
+        assert_eq!(sanitized_name(".."), "__");
+        assert_eq!(file_name(".Test", "json"), "_Test.json");
+    }
+
+    #[test]
+    fn should_split_and_join_content() {
+        let dir = std::env::temp_dir().join(format!("osc-variant-split-{}", std::process::id()));
+        let dirname = dir.to_string_lossy().to_string();
+
+        let mut data =
+            OnkostarEditor::from_str(include_str!("../libs/model/tests/test.osc")).unwrap();
+        split(&mut data, &dirname).unwrap();
+
+        assert!(dir.join("info.json").exists());
+        assert!(dir.join("data-forms").join("Hauptformular.json").exists());
+        assert!(
+            dir.join("unterformulare")
+                .join("Unterformular.json")
+                .exists()
+        );
+
+        let (joined, cycles) = join(&dirname).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(cycles.is_empty());
+        assert_eq!(
+            quick_xml::se::to_string(&joined).unwrap(),
+            quick_xml::se::to_string(&data).unwrap()
+        );
+    }
+
+    #[test]
+    fn should_keep_unknown_elements() {
+        let dir =
+            std::env::temp_dir().join(format!("osc-variant-split-lossless-{}", std::process::id()));
+        let dirname = dir.to_string_lossy().to_string();
+
+        let mut data = OnkostarEditor::from_str_lossless(
+            &include_str!("../libs/model/tests/test.osc").replacen(
+                "<TudokReadonly>",
+                "<Unbekannt>Test</Unbekannt><TudokReadonly>",
+                1,
+            ),
+        )
+        .unwrap();
+        assert_eq!(data.unknown_elements.len(), 1);
+        split(&mut data, &dirname).unwrap();
+
+        let (joined, _) = join(&dirname).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(joined.unknown_elements, data.unknown_elements);
+        assert!(
+            joined
+                .restore_unknown_elements(&quick_xml::se::to_string(&joined).unwrap())
+                .contains("<Unbekannt>Test</Unbekannt>")
+        );
+    }
+}